@@ -1,6 +1,6 @@
 use std::fmt::Write;
 
-use cocogitto::COMMITS_METADATA;
+use crate::COMMITS_METADATA;
 
 use anyhow::{bail, Result};
 use clap::builder::PossibleValuesParser;
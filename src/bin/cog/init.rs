@@ -0,0 +1,90 @@
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use crate::hook_kind;
+
+use anyhow::{bail, Result};
+use cocogitto::command::init::{InitOptions, Project};
+
+const HOOK_TYPES: [&str; 4] = ["commit-msg", "pre-push", "prepare-commit-msg", "all"];
+
+/// Ask for the `cog init` options on stdin, proposing what was detected in `path`.
+pub fn prompt_init_options(path: &Path) -> Result<InitOptions> {
+    let project = Project::detect(path)?;
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut options = InitOptions::default();
+
+    eprintln!("Detected a {}", project.kind);
+
+    if !project.packages.is_empty() {
+        for package in &project.packages {
+            eprintln!("  - {} ({})", package.name, package.path.display());
+        }
+
+        options.packages = confirm(
+            &mut input,
+            "Define these workspace members as monorepo packages?",
+        )?;
+    }
+
+    if let Some(origin) = &project.origin {
+        let question = format!("Use the '{origin}' origin remote for changelog links?");
+        options.remote = confirm(&mut input, &question)?;
+    }
+
+    let default_template = if options.remote { "remote" } else { "default" };
+    let template = ask(
+        &mut input,
+        "Changelog template (default, remote, full_hash or a path)",
+        default_template,
+    )?;
+    options.changelog_template = Some(template).filter(|template| template != "default");
+
+    let hooks = ask(
+        &mut input,
+        "Git hooks to install (commit-msg, pre-push, prepare-commit-msg, all or none)",
+        "none",
+    )?;
+
+    for hook in hooks.split(',').map(str::trim) {
+        match hook {
+            "none" | "" => {}
+            hook if HOOK_TYPES.contains(&hook) => options.hooks.push(hook_kind(hook)),
+            hook => bail!(
+                "unknown hook type '{}', expected one of {}",
+                hook,
+                HOOK_TYPES.join(", ")
+            ),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Read an answer, an empty line or the end of input selects `default`.
+fn ask(input: &mut impl BufRead, question: &str, default: &str) -> Result<String> {
+    eprint!("{question} [{default}]: ");
+    io::stderr().flush()?;
+
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+
+    match answer.trim() {
+        "" => Ok(default.to_string()),
+        answer => Ok(answer.to_string()),
+    }
+}
+
+fn confirm(input: &mut impl BufRead, question: &str) -> Result<bool> {
+    loop {
+        match ask(input, &format!("{question} (y/n)"), "y")?
+            .to_lowercase()
+            .as_str()
+        {
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => eprintln!("Please answer 'y' or 'n'"),
+        }
+    }
+}
@@ -1,21 +1,38 @@
 mod commit;
+mod init;
 
 use std::path::PathBuf;
 
+use cocogitto::command::init::InitOptions;
 use cocogitto::conventional::changelog::template::{RemoteContext, Template};
 use cocogitto::conventional::commit as conv_commit;
 use cocogitto::conventional::version::IncrementCommand;
-use cocogitto::git::hook::HookKind;
+use cocogitto::git::hook::{ExistingHook, HookKind};
 use cocogitto::git::revspec::RevspecPattern;
 use cocogitto::log::filter::{CommitFilter, CommitFilters};
 use cocogitto::log::output::Output;
-use cocogitto::{CocoGitto, SETTINGS};
+use cocogitto::settings::{ConfigSources, Settings};
+use cocogitto::{settings, CocoGitto, CogError, CommitsMetadata};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::builder::{PossibleValue, PossibleValuesParser};
 use clap::{ArgAction, ArgGroup, Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use clap_complete::{shells, Generator};
 use clap_complete_nushell::Nushell;
+use once_cell::sync::Lazy;
+
+// The clap value parsers need the settings of the current repository before any command runs,
+// they are loaded once here and shared with the commands
+static LOADED_SETTINGS: Lazy<Result<Settings, String>> =
+    Lazy::new(|| settings::try_load(&CONFIG_SOURCES).map_err(|err| err.to_string()));
+
+// The user config, `--config` file and `COG_*` environment variables, on top of the repository config
+static CONFIG_SOURCES: Lazy<ConfigSources> =
+    Lazy::new(|| ConfigSources::from_env(config_path_arg()));
+
+static SETTINGS: Lazy<Settings> = Lazy::new(|| LOADED_SETTINGS.clone().unwrap_or_default());
+
+pub(crate) static COMMITS_METADATA: Lazy<CommitsMetadata> = Lazy::new(|| SETTINGS.commit_types());
 
 fn hook_profiles() -> PossibleValuesParser {
     let profiles = SETTINGS
@@ -32,6 +49,16 @@ fn packages() -> PossibleValuesParser {
     profiles.into()
 }
 
+fn revspec_pattern(value: &str) -> Result<RevspecPattern, String> {
+    if value.contains("..") {
+        Ok(RevspecPattern::from(value))
+    } else {
+        Err(format!(
+            "invalid commit range pattern '{value}', expected `<from>..<to>`"
+        ))
+    }
+}
+
 /// Shell with auto-generated completion script available.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
@@ -115,6 +142,10 @@ struct Cli {
     #[arg(long, short = 'q')]
     quiet: bool,
 
+    /// Use this config file on top of the user and repository ones
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -130,6 +161,14 @@ enum Command {
         /// Ignore merge commits messages
         #[arg(short, long)]
         ignore_merge_commits: bool,
+
+        /// Check only the commits in the given revision range, e.g. `origin/main..HEAD`
+        #[arg(conflicts_with_all = ["from_latest_tag", "since_ref"], value_parser = revspec_pattern)]
+        range: Option<RevspecPattern>,
+
+        /// Check commits from the given ref (excluded) to HEAD, shorthand for `<REF>..HEAD`
+        #[arg(long, value_name = "REF", conflicts_with = "from_latest_tag")]
+        since_ref: Option<String>,
     },
 
     /// Create a new conventional commit
@@ -176,10 +215,11 @@ enum Command {
     },
 
     /// Display a changelog for the given commit oid range
+    #[command(group = ArgGroup::new("write-target").args(["full", "at"]))]
     Changelog {
         /// Generate the changelog in the given spec range
-        #[arg(conflicts_with = "at")]
-        pattern: Option<String>,
+        #[arg(conflicts_with = "at", value_parser = revspec_pattern)]
+        pattern: Option<RevspecPattern>,
 
         /// Generate the changelog for a specific git tag
         #[arg(short, long)]
@@ -203,6 +243,16 @@ enum Command {
         /// Name of the repository used during template generation
         #[arg(long, requires_all = ["owner", "remote"])]
         repository: Option<String>,
+
+        /// Generate the changelog of every version tag with the configured templates
+        #[arg(long, conflicts_with_all = ["pattern", "at", "template", "remote"])]
+        full: bool,
+
+        /// Write to the configured changelog files instead of printing.
+        /// With `--full`, every changelog is regenerated, keeping its header and footer.
+        /// With `--at`, only the release section of the tag is replaced
+        #[arg(short, long, requires = "write-target")]
+        write: bool,
     },
 
     /// Commit changelog from latest tag to HEAD and create new tag
@@ -240,7 +290,7 @@ enum Command {
         #[arg(long, value_parser = packages())]
         package: Option<String>,
 
-        /// Dry-run: print the target version. No action taken
+        /// Dry-run: print the target version and the hooks that would run. No action taken
         #[arg(short, long)]
         dry_run: bool,
     },
@@ -250,15 +300,57 @@ enum Command {
         /// Path to initialized dir
         #[arg(default_value = ".")]
         path: PathBuf,
+
+        /// Prompt for the packages, changelog and git hooks setup
+        #[arg(short, long, conflicts_with_all = ["packages", "remote", "template", "install_hook"])]
+        interactive: bool,
+
+        /// Define the detected Cargo or npm workspace members as monorepo packages
+        #[arg(long)]
+        packages: bool,
+
+        /// Set the changelog remote, owner and repository from the `origin` remote
+        #[arg(long)]
+        remote: bool,
+
+        /// Changelog template, either a predefined one (`default`, `remote`, `full_hash`) or a path
+        #[arg(long)]
+        template: Option<String>,
+
+        /// Git hook to install, can be repeated
+        #[arg(long, value_parser = ["commit-msg", "pre-push", "prepare-commit-msg", "all"])]
+        install_hook: Vec<String>,
     },
 
     /// Add git hooks to the repository
+    #[command(group = ArgGroup::new("existing").multiple(false))]
     InstallHook {
         /// Type of hook to install
-        #[arg(value_parser = ["commit-msg", "pre-push", "all"])]
+        #[arg(value_parser = ["commit-msg", "pre-push", "prepare-commit-msg", "all"])]
+        hook_type: String,
+
+        /// Replace existing hooks not installed by cog
+        #[arg(long, group = "existing")]
+        overwrite: bool,
+
+        /// Keep existing hooks not installed by cog and run them before cog hooks
+        #[arg(long, group = "existing")]
+        chain: bool,
+    },
+
+    /// Remove git hooks installed by cog, restoring chained hooks
+    UninstallHook {
+        /// Type of hook to remove
+        #[arg(value_parser = ["commit-msg", "pre-push", "prepare-commit-msg", "all"])]
         hook_type: String,
     },
 
+    /// Inspect cog configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+
     /// Generate shell completions
     GenerateCompletions {
         /// Shell to generate completions for
@@ -271,6 +363,18 @@ enum Command {
     GenerateManpage { cmd: String },
 }
 
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print the merged configuration and the origin of each value
+    Show,
+
+    /// Validate the configuration and report every problem found
+    Check,
+
+    /// Print the JSON Schema of the configuration, for editor completion and validation
+    Schema,
+}
+
 #[derive(Args)]
 struct CommitArgs {
     /// Conventional commit type
@@ -296,7 +400,72 @@ struct CommitArgs {
     sign: bool,
 }
 
+/// `--config` has to be known before parsing the command line,
+/// since the cli value parsers already read the settings.
+fn config_path_arg() -> Option<PathBuf> {
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        } else if arg == "--config" {
+            return args.next().map(PathBuf::from);
+        } else if let Some(path) = arg.to_str().and_then(|arg| arg.strip_prefix("--config=")) {
+            return Some(PathBuf::from(path));
+        }
+    }
+
+    None
+}
+
+/// Whether the command reads the settings, peeked from the arguments since the cli value parsers
+/// would otherwise silently fall back to the default settings on a malformed configuration.
+fn reads_settings_arg() -> bool {
+    let mut commands = vec![];
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.to_str() {
+            Some("--") => break,
+            Some("-h" | "--help" | "-V" | "--version") => return false,
+            Some("--config") => {
+                args.next();
+            }
+            Some(arg) if !arg.starts_with('-') => commands.push(arg.to_string()),
+            _ => {}
+        }
+
+        if commands.len() == 2 {
+            break;
+        }
+    }
+
+    let commands: Vec<&str> = commands.iter().map(String::as_str).collect();
+    !matches!(
+        commands.as_slice(),
+        [] | ["help", ..]
+            | ["init", ..]
+            | ["generate-completions", ..]
+            | ["generate-manpage", ..]
+            | ["config", "check" | "schema"]
+    )
+}
+
+/// Open the repository in the current directory with the already loaded settings
+fn cocogitto() -> Result<CocoGitto> {
+    CocoGitto::with_settings(&std::env::current_dir()?, SETTINGS.clone()).map_err(|err| match err {
+        // The git error is already displayed by `CogError::Git`, do not repeat it as its cause
+        CogError::Git(err) => err.into(),
+        err => err.into(),
+    })
+}
+
 fn main() -> Result<()> {
+    // Malformed configuration must not silently fall back to the defaults
+    if reads_settings_arg() {
+        if let Err(err) = &*LOADED_SETTINGS {
+            bail!("{}\nRun `cog config check` for details", err);
+        }
+    }
+
     let cli = Cli::parse();
 
     init_logs(cli.verbose, cli.quiet);
@@ -313,7 +482,7 @@ fn main() -> Result<()> {
             package,
             dry_run,
         } => {
-            let mut cocogitto = CocoGitto::get()?;
+            let cocogitto = cocogitto()?;
 
             let increment = match version {
                 Some(version) => IncrementCommand::Manual(version),
@@ -324,13 +493,13 @@ fn main() -> Result<()> {
                 _ => unreachable!(),
             };
 
-            let is_monorepo = !SETTINGS.packages.is_empty();
+            let is_monorepo = !cocogitto.settings().packages.is_empty();
 
             if is_monorepo {
                 match package {
                     Some(package_name) => {
                         // Safe unwrap here, package name is validated by clap
-                        let package = SETTINGS.packages.get(&package_name).unwrap();
+                        let package = cocogitto.settings().packages.get(&package_name).unwrap();
                         cocogitto.create_package_version(
                             (&package_name, package),
                             increment,
@@ -360,24 +529,32 @@ fn main() -> Result<()> {
             ignore_merge_commits,
         } => {
             let ignore_merge_commits = ignore_merge_commits || SETTINGS.ignore_merge_commits;
-            let author = CocoGitto::get()
+            let author = cocogitto()
                 .map(|cogito| cogito.get_committer().unwrap())
                 .ok();
 
-            conv_commit::verify(author, &message, ignore_merge_commits)?;
+            conv_commit::verify(author, &message, ignore_merge_commits, &SETTINGS)?;
         }
         Command::Check {
             from_latest_tag,
             ignore_merge_commits,
+            range,
+            since_ref,
         } => {
-            let cocogitto = CocoGitto::get()?;
-            let from_latest_tag = from_latest_tag || SETTINGS.from_latest_tag;
-            let ignore_merge_commits = ignore_merge_commits || SETTINGS.ignore_merge_commits;
-            cocogitto.check(from_latest_tag, ignore_merge_commits)?;
+            let cocogitto = cocogitto()?;
+            let from_latest_tag = from_latest_tag || cocogitto.settings().from_latest_tag;
+            let ignore_merge_commits =
+                ignore_merge_commits || cocogitto.settings().ignore_merge_commits;
+            let range = range.or_else(|| {
+                since_ref
+                    .as_deref()
+                    .map(|since_ref| RevspecPattern::from((since_ref, "HEAD")))
+            });
+            cocogitto.check(from_latest_tag, ignore_merge_commits, range)?;
         }
         Command::Edit { from_latest_tag } => {
-            let cocogitto = CocoGitto::get()?;
-            let from_latest_tag = from_latest_tag || SETTINGS.from_latest_tag;
+            let cocogitto = cocogitto()?;
+            let from_latest_tag = from_latest_tag || cocogitto.settings().from_latest_tag;
             cocogitto.check_and_edit(from_latest_tag)?;
         }
         Command::Log {
@@ -387,7 +564,7 @@ fn main() -> Result<()> {
             scope,
             no_error,
         } => {
-            let cocogitto = CocoGitto::get()?;
+            let cocogitto = cocogitto()?;
 
             let repo_tag_name = cocogitto.get_repo_tag_name();
             let repo_tag_name = repo_tag_name.as_deref().unwrap_or("cog log");
@@ -437,41 +614,101 @@ fn main() -> Result<()> {
             remote,
             owner,
             repository,
+            full,
+            write,
         } => {
-            let cocogitto = CocoGitto::get()?;
+            let cocogitto = cocogitto()?;
 
-            let context = RemoteContext::try_new(remote, repository, owner)
-                .or_else(|| SETTINGS.get_template_context());
-            let template = template.as_ref().or(SETTINGS.changelog.template.as_ref());
-            let template = if let Some(template) = template {
-                Template::from_arg(template, context)?
+            if write {
+                match at {
+                    Some(tag) => {
+                        cocogitto.rewrite_changelog_version(&tag)?;
+                    }
+                    None => {
+                        cocogitto.write_full_changelog()?;
+                    }
+                }
+            } else if full {
+                println!("{}", cocogitto.get_full_changelog()?);
+            } else {
+                let settings = cocogitto.settings();
+                let context = match RemoteContext::try_new(remote, repository, owner)? {
+                    Some(context) => Some(context.with_platform(settings.changelog.platform)),
+                    None => settings.get_template_context()?,
+                };
+                let template = template.as_ref().or(settings.changelog.template.as_ref());
+                let template = if let Some(template) = template {
+                    Template::from_arg(template, context)?
+                } else {
+                    Template::default()
+                };
+
+                let result = match at {
+                    Some(at) => cocogitto.get_changelog_at_tag(&at, template)?,
+                    None => {
+                        let changelog =
+                            cocogitto.get_changelog(pattern.unwrap_or_default(), true)?;
+                        changelog.into_markdown(template, settings)?
+                    }
+                };
+                println!("{}", result);
+            }
+        }
+        Command::Init {
+            path,
+            interactive,
+            packages,
+            remote,
+            template,
+            install_hook,
+        } => {
+            let options = if interactive {
+                init::prompt_init_options(&path)?
             } else {
-                Template::default()
+                InitOptions {
+                    packages,
+                    remote,
+                    changelog_template: template,
+                    hooks: install_hook.iter().map(|hook| hook_kind(hook)).collect(),
+                }
             };
 
-            let pattern = pattern.as_deref().map(RevspecPattern::from);
-
-            let result = match at {
-                Some(at) => cocogitto.get_changelog_at_tag(&at, template)?,
-                None => {
-                    let changelog = cocogitto.get_changelog(pattern.unwrap_or_default(), true)?;
-                    changelog.into_markdown(template)?
-                }
+            cocogitto::command::init::init_with_options(&path, &options)?;
+        }
+        Command::InstallHook {
+            hook_type,
+            overwrite,
+            chain,
+        } => {
+            let cocogitto = cocogitto()?;
+            let existing = match (overwrite, chain) {
+                (true, _) => ExistingHook::Overwrite,
+                (_, true) => ExistingHook::Chain,
+                _ => ExistingHook::Refuse,
             };
-            println!("{}", result);
+            cocogitto.install_hook(hook_kind(&hook_type), existing)?;
         }
-        Command::Init { path } => {
-            cocogitto::command::init::init(&path)?;
+        Command::UninstallHook { hook_type } => {
+            let cocogitto = cocogitto()?;
+            cocogitto.uninstall_hook(hook_kind(&hook_type))?;
         }
-        Command::InstallHook { hook_type } => {
-            let cocogitto = CocoGitto::get()?;
-            match hook_type.as_str() {
-                "commit-msg" => cocogitto.install_hook(HookKind::PrepareCommit)?,
-                "pre-push" => cocogitto.install_hook(HookKind::PrePush)?,
-                "all" => cocogitto.install_hook(HookKind::All)?,
-                _ => unreachable!(),
+        Command::Config { command } => match command {
+            ConfigCommand::Show => {
+                for entry in settings::config_entries(&CONFIG_SOURCES)? {
+                    println!("{}", entry);
+                }
             }
-        }
+            ConfigCommand::Schema => println!("{}", settings::json_schema()),
+            ConfigCommand::Check => {
+                let diagnostics = settings::check_config(&CONFIG_SOURCES);
+                if !diagnostics.is_empty() {
+                    let diagnostics: Vec<_> = diagnostics.iter().map(ToString::to_string).collect();
+                    bail!("invalid configuration\n\n{}", diagnostics.join("\n"));
+                }
+
+                eprintln!("No configuration problem found");
+            }
+        },
         Command::GenerateCompletions { shell } => {
             clap_complete::generate(shell, &mut Cli::command(), "cog", &mut std::io::stdout());
         }
@@ -496,7 +733,7 @@ fn main() -> Result<()> {
             edit,
             sign,
         }) => {
-            let cocogitto = CocoGitto::get()?;
+            let cocogitto = cocogitto()?;
             let (body, footer, breaking) = if edit {
                 commit::edit_message(&typ, &message, scope.as_deref(), breaking_change)?
             } else {
@@ -511,6 +748,16 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+fn hook_kind(hook_type: &str) -> HookKind {
+    match hook_type {
+        "commit-msg" => HookKind::PrepareCommit,
+        "pre-push" => HookKind::PrePush,
+        "prepare-commit-msg" => HookKind::PrepareCommitMsg,
+        "all" => HookKind::All,
+        _ => unreachable!(),
+    }
+}
+
 fn init_logs(verbose: u8, quiet: bool) {
     let verbosity = if verbose == 0 { 2 } else { verbose - 1 };
     stderrlog::new()
@@ -1,76 +1,208 @@
 use crate::conventional::changelog::release::Release;
 use crate::conventional::commit::Commit;
-use crate::git::error::TagError;
+use crate::error::CogError;
+use crate::git::error::{Git2Error, TagError};
 use crate::git::hook::Hooks;
 use crate::git::oid::OidOf;
 use crate::git::revspec::RevspecPattern;
 use crate::git::tag::Tag;
-use crate::hook::{Hook, HookVersion};
+use crate::hook::{BumpedPackage, Hook, HookContext, HookVersion};
 use crate::settings::{HookType, MonoRepoPackage, Settings};
-use crate::BumpError;
-use crate::{CocoGitto, SETTINGS};
+use crate::CocoGitto;
+use anyhow::Context;
 use anyhow::Result;
-use anyhow::{anyhow, bail, ensure, Context};
 use colored::Colorize;
 use conventional_commit_parser::commit::CommitType;
+use git2::Oid;
 use globset::Glob;
 use itertools::Itertools;
 use log::{error, info, warn};
 use std::fmt;
 use std::fmt::Write;
+use std::path::Path;
 use std::process::exit;
+use std::thread;
 
 mod monorepo;
 mod package;
 mod standard;
 
-fn ensure_tag_is_greater_than_previous(current: &Tag, next: &Tag) -> Result<()> {
-    if next <= current {
-        let comparison = format!("{} <= {}", current, next).red();
-        let cause_key = "cause:".red();
-        let cause = format!(
-            "{} version MUST be greater than current one: {}",
-            cause_key, comparison
-        );
+pub use standard::{AppliedBump, BumpPlan};
 
-        bail!("{}:\n\t{}\n", "SemVer Error".red().to_string(), cause);
+fn ensure_tag_is_greater_than_previous(current: &Tag, next: &Tag) -> Result<(), CogError> {
+    if next <= current {
+        return Err(CogError::VersionNotGreater {
+            current: current.to_string(),
+            next: next.to_string(),
+        });
     };
 
     Ok(())
 }
 
-fn tag_or_fallback_to_zero(tag: Result<Tag, TagError>) -> Result<Tag> {
+fn tag_or_fallback_to_zero(tag: Result<Tag, TagError>) -> Result<Tag, TagError> {
     match tag {
-        Ok(ref tag) => Ok(tag.clone()),
-        Err(ref err) if err == &TagError::NoTag => Ok(Tag::default()),
-        Err(err) => Err(anyhow!(err)),
+        Err(TagError::NoTag) => Ok(Tag::default()),
+        tag => tag,
+    }
+}
+
+/// Exit after a hook failure has been stashed, leaving other errors to the caller.
+pub(crate) fn exit_on_hook_failure<T>(result: Result<T, CogError>) -> Result<T> {
+    match result {
+        Ok(res) => Ok(res),
+        Err(err @ CogError::HookFailed { .. }) => {
+            error!("{}", err);
+            exit(1);
+        }
+        Err(CogError::Other(err)) => Err(err),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn get_hooks(
+    settings: &Settings,
+    hook_type: HookType,
+    hook_profile: Option<&str>,
+    package: Option<&MonoRepoPackage>,
+) -> Result<Vec<Hook>> {
+    let hooks = match (package, hook_profile) {
+        (None, Some(profile)) => settings
+            .get_profile_hooks(profile, hook_type)
+            .iter()
+            .map(Hook::try_from)
+            .enumerate()
+            .map(|(idx, result)| {
+                result.context(format!(
+                    "Cannot parse bump profile {} hook at index {}",
+                    profile, idx
+                ))
+            })
+            .try_collect()?,
+
+        (Some(package), Some(profile)) => {
+            let package_hooks = package.hooks(settings);
+            let hooks = package_hooks.get_profile_hooks(profile, hook_type);
+
+            hooks
+                .iter()
+                .map(Hook::try_from)
+                .enumerate()
+                .map(|(idx, result)| {
+                    result.context(format!(
+                        "Cannot parse bump profile {} hook at index {}",
+                        profile, idx
+                    ))
+                })
+                .try_collect()?
+        }
+        (Some(package), None) => package
+            .hooks(settings)
+            .get_hooks(hook_type)
+            .iter()
+            .map(Hook::try_from)
+            .enumerate()
+            .map(|(idx, result)| result.context(format!("Cannot parse hook at index {}", idx)))
+            .try_collect()?,
+        (None, None) => settings
+            .get_hooks(hook_type)
+            .iter()
+            .map(Hook::try_from)
+            .enumerate()
+            .map(|(idx, result)| result.context(format!("Cannot parse hook at index {}", idx)))
+            .try_collect()?,
+    };
+
+    Ok(hooks)
+}
+
+fn print_hook_header(hook_type: HookType, package_name: Option<&str>) {
+    let hook_type = match hook_type {
+        HookType::PreBump => "pre-bump",
+        HookType::PostBump => "post-bump",
+        HookType::PreChangelog => "pre-changelog",
+        HookType::PostChangelog => "post-changelog",
+        HookType::PreCommit => "pre-commit",
+        HookType::PreTag => "pre-tag",
+        HookType::OnFailure => "on-failure",
+    };
+
+    match package_name {
+        None => {
+            let msg = format!("[{hook_type}]").underline().white().bold();
+            info!("{msg}")
+        }
+        Some(package_name) => {
+            let msg = format!("[{hook_type}-{package_name}]")
+                .underline()
+                .white()
+                .bold();
+            info!("{msg}")
+        }
     }
 }
 
 impl CocoGitto {
-    pub fn unwrap_or_stash_and_exit<T>(&mut self, tag: &Tag, result: Result<T>) -> T {
-        match result {
+    pub fn unwrap_or_stash_and_exit<T>(&self, tag: &Tag, result: Result<T>) -> T {
+        match self.stash_on_failure(tag, result) {
             Ok(res) => res,
             Err(err) => {
-                self.repository
-                    .stash_failed_version(tag.clone())
-                    .expect("stash");
-                error!(
-                    "{}",
-                    BumpError {
-                        cause: err.to_string(),
-                        version: tag.to_string(),
-                        stash_number: 0,
-                    }
-                );
+                error!("{}", err);
+                exit(1);
+            }
+        }
+    }
 
+    pub fn unwrap_or_reset_and_exit<T>(
+        &self,
+        version_commit: Oid,
+        tag: &Tag,
+        result: Result<T>,
+    ) -> T {
+        match self.reset_on_failure(version_commit, tag, result) {
+            Ok(res) => res,
+            Err(err) => {
+                error!("{}", err);
                 exit(1);
             }
         }
     }
 
-    fn pre_bump_checks(&mut self) -> Result<()> {
-        if *SETTINGS == Settings::default() {
+    /// Undo the version commit when a hook run after it fails, its changes are then stashed
+    /// like the ones of a bump failing before the commit.
+    fn reset_on_failure<T>(
+        &self,
+        version_commit: Oid,
+        tag: &Tag,
+        result: Result<T>,
+    ) -> Result<T, CogError> {
+        if result.is_err() {
+            self.repository.reset_commit(version_commit)?;
+        }
+
+        self.stash_on_failure(tag, result)
+    }
+
+    /// Stash the changes made during a failed bump, the hook error is returned as [`CogError::HookFailed`].
+    fn stash_on_failure<T>(&self, tag: &Tag, result: Result<T>) -> Result<T, CogError> {
+        match result {
+            Ok(res) => Ok(res),
+            Err(cause) => {
+                // Hooks failing before the changelog is written may leave nothing to stash
+                if !self.repository.get_statuses()?.0.is_empty() {
+                    self.repository.stash_failed_version(tag.clone())?;
+                }
+
+                Err(CogError::HookFailed {
+                    version: tag.to_string(),
+                    cause,
+                })
+            }
+        }
+    }
+
+    fn pre_bump_checks(&self) -> Result<(), CogError> {
+        if *self.settings() == Settings::default() {
             let part1 = "Warning: using".yellow();
             let part2 = "with the default configuration. \n".yellow();
             let part3 = "You may want to create a".yellow();
@@ -83,24 +215,30 @@ impl CocoGitto {
         let statuses = self.repository.get_statuses()?;
 
         // Fail if repo contains un-staged or un-committed changes
-        ensure!(statuses.0.is_empty(), "{}", self.repository.get_statuses()?);
+        if !statuses.0.is_empty() {
+            return Err(CogError::DirtyWorkingTree(statuses));
+        }
 
-        if !SETTINGS.branch_whitelist.is_empty() {
+        if !self.settings().branch_whitelist.is_empty() {
             if let Some(branch) = self.repository.get_branch_shorthand() {
-                let whitelist = &SETTINGS.branch_whitelist;
-                let is_match = whitelist.iter().any(|pattern| {
+                let whitelist = &self.settings().branch_whitelist;
+                let mut is_match = false;
+                for pattern in whitelist {
                     let glob = Glob::new(pattern)
-                        .expect("invalid glob pattern")
+                        .map_err(|err| CogError::InvalidBranchPattern {
+                            pattern: pattern.clone(),
+                            err,
+                        })?
                         .compile_matcher();
-                    glob.is_match(&branch)
-                });
+                    is_match |= glob.is_match(&branch);
+                }
 
-                ensure!(
-                    is_match,
-                    "No patterns matched in {:?} for branch '{}', bump is not allowed",
-                    whitelist,
-                    branch
-                )
+                if !is_match {
+                    return Err(CogError::BranchNotAllowed {
+                        branch,
+                        whitelist: whitelist.clone(),
+                    });
+                }
             }
         };
 
@@ -112,10 +250,10 @@ impl CocoGitto {
         &self,
         pattern: RevspecPattern,
         tag: Tag,
-    ) -> Result<Release> {
+    ) -> Result<Release, Git2Error> {
         let commit_range = self.repository.get_commit_range(&pattern)?;
 
-        let mut release = Release::from(commit_range);
+        let mut release = Release::from_commit_range(commit_range, self.settings());
         release.version = OidOf::Tag(tag);
         Ok(release)
     }
@@ -126,12 +264,12 @@ impl CocoGitto {
         pattern: RevspecPattern,
         tag: Tag,
         package: &str,
-    ) -> Result<Release> {
+    ) -> Result<Release, Git2Error> {
         let commit_range = self
             .repository
             .get_commit_range_for_package(&pattern, package)?;
 
-        let mut release = Release::from(commit_range);
+        let mut release = Release::from_commit_range(commit_range, self.settings());
         release.version = OidOf::Tag(tag);
         Ok(release)
     }
@@ -141,16 +279,18 @@ impl CocoGitto {
         &self,
         pattern: RevspecPattern,
         tag: Tag,
-    ) -> Result<Release> {
+    ) -> Result<Release, Git2Error> {
         let commit_range = self
             .repository
             .get_commit_range_for_monorepo_global(&pattern)?;
 
-        let mut release = Release::from(commit_range);
+        let mut release = Release::from_commit_range(commit_range, self.settings());
         release.version = OidOf::Tag(tag);
         Ok(release)
     }
 
+    /// Run the hooks of the given stage, `on_failure` hooks are run if any of them fails.
+    #[allow(clippy::too_many_arguments)]
     fn run_hooks(
         &self,
         hook_type: HookType,
@@ -159,93 +299,254 @@ impl CocoGitto {
         hook_profile: Option<&str>,
         package_name: Option<&str>,
         package: Option<&MonoRepoPackage>,
+        changelog: Option<&str>,
     ) -> Result<()> {
-        let settings = Settings::get(&self.repository)?;
+        let settings = self.settings();
+        let context = self.hook_context(settings, package_name, package, changelog, false);
+        self.run_hooks_with_context(
+            settings,
+            hook_type,
+            current_tag,
+            next_version,
+            hook_profile,
+            package,
+            &context,
+        )
+    }
 
-        let hooks: Vec<Hook> = match (package, hook_profile) {
-            (None, Some(profile)) => settings
-                .get_profile_hooks(profile, hook_type)
-                .iter()
-                .map(|s| s.parse())
-                .enumerate()
-                .map(|(idx, result)| {
-                    result.context(format!(
-                        "Cannot parse bump profile {} hook at index {}",
-                        profile, idx
-                    ))
-                })
-                .try_collect()?,
-
-            (Some(package), Some(profile)) => {
-                let hooks = package.get_profile_hooks(profile, hook_type);
-
-                hooks
-                    .iter()
-                    .map(|s| s.parse())
-                    .enumerate()
-                    .map(|(idx, result)| {
-                        result.context(format!(
-                            "Cannot parse bump profile {} hook at index {}",
-                            profile, idx
-                        ))
-                    })
-                    .try_collect()?
+    /// Run global monorepo hooks, exposing the packages bumped alongside the global version
+    /// with `{{packages}}` and a JSON file at `COG_PACKAGES_FILE`.
+    fn run_global_monorepo_hooks(
+        &self,
+        hook_type: HookType,
+        current_tag: Option<&HookVersion>,
+        next_version: &HookVersion,
+        hook_profile: Option<&str>,
+        changelog: Option<&str>,
+        packages: &[BumpedPackage],
+    ) -> Result<()> {
+        let settings = self.settings();
+        let mut context = self.hook_context(settings, None, None, changelog, false);
+        context.packages = Some(packages.to_vec());
+        self.run_hooks_with_context(
+            settings,
+            hook_type,
+            current_tag,
+            next_version,
+            hook_profile,
+            None,
+            &context,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn run_hooks_with_context(
+        &self,
+        settings: &Settings,
+        hook_type: HookType,
+        current_tag: Option<&HookVersion>,
+        next_version: &HookVersion,
+        hook_profile: Option<&str>,
+        package: Option<&MonoRepoPackage>,
+        context: &HookContext,
+    ) -> Result<()> {
+        let outcome = self.run_hook_stage(
+            settings,
+            hook_type,
+            current_tag,
+            next_version,
+            hook_profile,
+            package,
+            context,
+        );
+
+        if outcome.is_err() && !matches!(hook_type, HookType::OnFailure) {
+            if let Err(err) = self.run_hook_stage(
+                settings,
+                HookType::OnFailure,
+                current_tag,
+                next_version,
+                hook_profile,
+                package,
+                context,
+            ) {
+                error!("on-failure hook failed: {err}");
             }
-            (Some(package), None) => package
-                .get_hooks(hook_type)
-                .iter()
-                .map(|s| s.parse())
-                .enumerate()
-                .map(|(idx, result)| result.context(format!("Cannot parse hook at index {}", idx)))
-                .try_collect()?,
-            (None, None) => settings
-                .get_hooks(hook_type)
-                .iter()
-                .map(|s| s.parse())
-                .enumerate()
-                .map(|(idx, result)| result.context(format!("Cannot parse hook at index {}", idx)))
-                .try_collect()?,
-        };
+        }
+
+        outcome
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn run_hook_stage(
+        &self,
+        settings: &Settings,
+        hook_type: HookType,
+        current_tag: Option<&HookVersion>,
+        next_version: &HookVersion,
+        hook_profile: Option<&str>,
+        package: Option<&MonoRepoPackage>,
+        context: &HookContext,
+    ) -> Result<()> {
+        let hooks = get_hooks(settings, hook_type, hook_profile, package)?;
 
         if !hooks.is_empty() {
-            let hook_type = match hook_type {
-                HookType::PreBump => "pre-bump",
-                HookType::PostBump => "post-bump",
-            };
+            print_hook_header(hook_type, context.package.as_deref());
+        }
 
-            match package_name {
+        let mut env = context.env(current_tag, next_version);
+
+        // Kept alive until every hook of the stage has run
+        let packages_file = match &context.packages {
+            Some(packages) if !hooks.is_empty() => {
+                let mut file = tempfile::Builder::new()
+                    .prefix("cog-packages-")
+                    .suffix(".json")
+                    .tempfile()?;
+                serde_json::to_writer_pretty(&mut file, packages)?;
+                env.push((
+                    "COG_PACKAGES_FILE",
+                    file.path().to_string_lossy().to_string(),
+                ));
+                Some(file)
+            }
+            _ => None,
+        };
+
+        let mut runnable = vec![];
+        for mut hook in hooks {
+            hook.insert_versions(current_tag, next_version, context)?;
+            if hook.should_run(current_tag, next_version, context)? {
+                runnable.push(hook);
+            } else {
+                info!("[skipped] [{hook}]");
+            }
+        }
+
+        // Hooks run from the repository, or the package directory
+        let base_dir = self
+            .repository
+            .resolve_path(package.map_or(Path::new("."), |p| p.path.as_path()));
+        for (group, hooks) in &runnable.iter().group_by(|hook| hook.parallel_group()) {
+            match group {
                 None => {
-                    let msg = format!("[{hook_type}]").underline().white().bold();
-                    info!("{msg}")
+                    for hook in hooks {
+                        let command = hook.to_string();
+                        let command = if command.chars().count() > 78 {
+                            &command[0..command.len()]
+                        } else {
+                            &command
+                        };
+                        info!("[{command}]");
+                        hook.run(Some(&base_dir), &env).context(hook.to_string())?;
+                        info!("");
+                    }
                 }
-                Some(package_name) => {
-                    let msg = format!("[{hook_type}-{package_name}]")
-                        .underline()
-                        .white()
-                        .bold();
-                    info!("{msg}")
+                // Hooks in the same parallel group run concurrently, the group fails if any of them fails
+                Some(group) => {
+                    let hooks: Vec<&Hook> = hooks.collect();
+                    info!("[parallel: {group}]");
+                    for hook in &hooks {
+                        info!("[{hook}]");
+                    }
+
+                    thread::scope(|scope| {
+                        let handles: Vec<_> = hooks
+                            .iter()
+                            .map(|hook| {
+                                scope.spawn(|| {
+                                    hook.run(Some(&base_dir), &env).context(hook.to_string())
+                                })
+                            })
+                            .collect();
+
+                        handles
+                            .into_iter()
+                            .map(|handle| handle.join().expect("hook thread panicked"))
+                            .collect::<Result<Vec<()>>>()
+                    })?;
+                    info!("");
                 }
             }
         }
 
-        for mut hook in hooks {
-            hook.insert_versions(current_tag, next_version)?;
-            let command = hook.to_string();
-            let command = if command.chars().count() > 78 {
-                &command[0..command.len()]
-            } else {
-                &command
-            };
-            info!("[{command}]");
-            let package_path = package.map(|p| p.path.as_path());
-            hook.run(package_path).context(hook.to_string())?;
-            println!();
+        drop(packages_file);
+        Ok(())
+    }
+
+    /// Parse and interpolate the hooks of every stage without running them,
+    /// printing the commands that would be executed.
+    fn dry_run_hooks(
+        &self,
+        current_tag: Option<&HookVersion>,
+        next_version: &HookVersion,
+        hook_profile: Option<&str>,
+        package_name: Option<&str>,
+        package: Option<&MonoRepoPackage>,
+        packages: Option<&[BumpedPackage]>,
+    ) -> Result<()> {
+        let settings = self.settings();
+        let mut context = self.hook_context(settings, package_name, package, None, true);
+        context.packages = packages.map(<[BumpedPackage]>::to_vec);
+
+        for hook_type in [
+            HookType::PreChangelog,
+            HookType::PostChangelog,
+            HookType::PreBump,
+            HookType::PreCommit,
+            HookType::PreTag,
+            HookType::PostBump,
+            HookType::OnFailure,
+        ] {
+            let hooks = get_hooks(settings, hook_type, hook_profile, package)?;
+
+            if !hooks.is_empty() {
+                print_hook_header(hook_type, package_name);
+            }
+
+            for mut hook in hooks {
+                hook.insert_versions(current_tag, next_version, &context)
+                    .context(hook.to_string())?;
+                if hook.should_run(current_tag, next_version, &context)? {
+                    info!("[{hook}]");
+                } else {
+                    info!("[skipped] [{hook}]");
+                }
+            }
         }
 
         Ok(())
     }
 
-    fn get_revspec_for_tag(&mut self, tag: &Tag) -> Result<RevspecPattern> {
+    fn hook_context(
+        &self,
+        settings: &Settings,
+        package_name: Option<&str>,
+        package: Option<&MonoRepoPackage>,
+        changelog: Option<&str>,
+        dry_run: bool,
+    ) -> HookContext {
+        HookContext {
+            package: package_name.map(str::to_string),
+            package_path: package.map(|package| package.path.clone()),
+            changelog_path: Some(
+                package
+                    .map(MonoRepoPackage::changelog_path)
+                    .unwrap_or_else(|| settings.changelog.path.clone()),
+            ),
+            branch: self.repository.get_branch_shorthand(),
+            commit_sha: self
+                .repository
+                .get_head_commit_oid()
+                .ok()
+                .map(|oid| oid.to_string()),
+            changelog: changelog.map(str::to_string),
+            dry_run,
+            packages: None,
+        }
+    }
+
+    fn get_revspec_for_tag(&self, tag: &Tag) -> Result<RevspecPattern, Git2Error> {
         let origin = if tag.is_zero() {
             self.repository.get_first_commit()?.to_string()
         } else {
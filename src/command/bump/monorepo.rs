@@ -8,14 +8,15 @@ use crate::conventional::changelog::ReleaseType;
 use crate::conventional::version::{Increment, IncrementCommand};
 
 use crate::git::tag::Tag;
-use crate::hook::HookVersion;
+use crate::hook::{BumpedPackage, HookVersion};
 use crate::settings::HookType;
-use crate::{settings, CocoGitto, SETTINGS};
+use crate::CocoGitto;
 use anyhow::Result;
 use colored::*;
 
 use log::info;
 use semver::Prerelease;
+use std::path::PathBuf;
 
 use crate::conventional::error::BumpError;
 use crate::git::oid::OidOf;
@@ -29,6 +30,22 @@ struct PackageBumpData {
     increment: Increment,
 }
 
+impl From<&PackageBumpData> for BumpedPackage {
+    fn from(bump: &PackageBumpData) -> Self {
+        let tag = &bump.new_version.prefixed_tag;
+        BumpedPackage {
+            name: bump.package_name.clone(),
+            path: PathBuf::from(&bump.package_path),
+            previous_version: bump
+                .old_version
+                .as_ref()
+                .map(|version| version.prefixed_tag.version.to_string()),
+            version: tag.version.to_string(),
+            tag: tag.to_string(),
+        }
+    }
+}
+
 struct PackageData {
     package_name: String,
     package_path: String,
@@ -37,7 +54,7 @@ struct PackageData {
 
 impl CocoGitto {
     pub fn create_monorepo_version(
-        &mut self,
+        &self,
         increment: IncrementCommand,
         pre_release: Option<&str>,
         hooks_config: Option<&str>,
@@ -52,7 +69,7 @@ impl CocoGitto {
     }
 
     fn create_monorepo_version_auto(
-        &mut self,
+        &self,
         pre_release: Option<&str>,
         hooks_config: Option<&str>,
         dry_run: bool,
@@ -60,6 +77,7 @@ impl CocoGitto {
         self.pre_bump_checks()?;
         // Get package bumps
         let bumps = self.get_packages_bumps(pre_release)?;
+        let bumped_packages: Vec<BumpedPackage> = bumps.iter().map(BumpedPackage::from).collect();
 
         // Get the greatest package increment among public api packages
         let increment_from_package_bumps = bumps
@@ -81,9 +99,36 @@ impl CocoGitto {
             tag.version.pre = Prerelease::new(pre_release)?;
         }
 
-        let tag = Tag::create(tag.version, None);
+        let tag = Tag::create(tag.version, None, self.settings());
 
         if dry_run {
+            for bump in &bumps {
+                let package = self
+                    .settings()
+                    .packages
+                    .get(&bump.package_name)
+                    .expect("package exists");
+                self.dry_run_hooks(
+                    bump.old_version.as_ref(),
+                    &bump.new_version,
+                    hooks_config,
+                    Some(&bump.package_name),
+                    Some(package),
+                    None,
+                )?;
+            }
+
+            let current = self.repository.get_latest_tag().map(HookVersion::new).ok();
+            let next_version = HookVersion::new(tag.clone());
+            self.dry_run_hooks(
+                current.as_ref(),
+                &next_version,
+                hooks_config,
+                None,
+                None,
+                Some(&bumped_packages),
+            )?;
+
             for bump in bumps {
                 println!("{}", bump.new_version.prefixed_tag)
             }
@@ -118,42 +163,134 @@ impl CocoGitto {
 
         changelog.pretty_print_bump_summary()?;
 
-        let path = settings::changelog_path();
-        let template = SETTINGS.get_monorepo_changelog_template()?;
+        let path = &self
+            .repository
+            .resolve_path(&self.settings().changelog.path);
+        let template = self.settings().get_monorepo_changelog_template()?;
+
+        let current = self.repository.get_latest_tag().map(HookVersion::new).ok();
+        let next_version = HookVersion::new(tag.clone());
 
-        changelog.write_to_file(
+        let hook_result = self.run_global_monorepo_hooks(
+            HookType::PreChangelog,
+            current.as_ref(),
+            &next_version,
+            hooks_config,
+            None,
+            &bumped_packages,
+        );
+
+        self.repository.add_all()?;
+        self.unwrap_or_stash_and_exit(&tag, hook_result);
+
+        let changelog = changelog.write_to_file(
             path,
             template,
             ReleaseType::MonoRepo(MonoRepoContext {
                 package_lock: false,
                 packages: template_context,
             }),
+            self.settings(),
         )?;
 
-        let current = self.repository.get_latest_tag().map(HookVersion::new).ok();
-        let next_version = HookVersion::new(tag.clone());
+        let hook_result = self
+            .run_global_monorepo_hooks(
+                HookType::PostChangelog,
+                current.as_ref(),
+                &next_version,
+                hooks_config,
+                Some(&changelog),
+                &bumped_packages,
+            )
+            .and_then(|_| {
+                self.run_global_monorepo_hooks(
+                    HookType::PreBump,
+                    current.as_ref(),
+                    &next_version,
+                    hooks_config,
+                    Some(&changelog),
+                    &bumped_packages,
+                )
+            });
+
+        self.repository.add_all()?;
 
-        let hook_result = self.run_hooks(
-            HookType::PreBump,
+        self.unwrap_or_stash_and_exit(&tag, hook_result);
+
+        let package_changelogs = self.bump_packages(pre_release, hooks_config, &bumps)?;
+
+        // Run per package and global pre commit hooks
+        for (bump, package_changelog) in bumps.iter().zip(&package_changelogs) {
+            let package = self
+                .settings()
+                .packages
+                .get(&bump.package_name)
+                .expect("package exists");
+            let hook_result = self.run_hooks(
+                HookType::PreCommit,
+                bump.old_version.as_ref(),
+                &bump.new_version,
+                hooks_config,
+                Some(&bump.package_name),
+                Some(package),
+                Some(package_changelog),
+            );
+
+            self.repository.add_all()?;
+            self.unwrap_or_stash_and_exit(&tag, hook_result);
+        }
+
+        let hook_result = self.run_global_monorepo_hooks(
+            HookType::PreCommit,
             current.as_ref(),
             &next_version,
             hooks_config,
-            None,
-            None,
+            Some(&changelog),
+            &bumped_packages,
         );
 
         self.repository.add_all()?;
-
         self.unwrap_or_stash_and_exit(&tag, hook_result);
 
-        self.bump_packages(pre_release, hooks_config, &bumps)?;
-
         let sign = self.repository.gpg_sign();
-        self.repository.commit(
+        let oid = self.repository.commit(
             &format!("chore(version): {}", next_version.prefixed_tag),
             sign,
         )?;
 
+        // Every pre-tag hook runs before the first tag is created, so a failure can be rolled back
+        let hook_result = bumps
+            .iter()
+            .zip(&package_changelogs)
+            .try_for_each(|(bump, package_changelog)| {
+                let package = self
+                    .settings()
+                    .packages
+                    .get(&bump.package_name)
+                    .expect("package exists");
+                self.run_hooks(
+                    HookType::PreTag,
+                    bump.old_version.as_ref(),
+                    &bump.new_version,
+                    hooks_config,
+                    Some(&bump.package_name),
+                    Some(package),
+                    Some(package_changelog),
+                )
+            })
+            .and_then(|_| {
+                self.run_global_monorepo_hooks(
+                    HookType::PreTag,
+                    current.as_ref(),
+                    &next_version,
+                    hooks_config,
+                    Some(&changelog),
+                    &bumped_packages,
+                )
+            });
+
+        self.unwrap_or_reset_and_exit(oid, &tag, hook_result);
+
         for bump in &bumps {
             self.repository.create_tag(&bump.new_version.prefixed_tag)?;
         }
@@ -161,8 +298,9 @@ impl CocoGitto {
         self.repository.create_tag(&tag)?;
 
         // Run per package post hooks
-        for bump in bumps {
-            let package = SETTINGS
+        for (bump, package_changelog) in bumps.iter().zip(package_changelogs) {
+            let package = self
+                .settings()
                 .packages
                 .get(&bump.package_name)
                 .expect("package exists");
@@ -173,24 +311,25 @@ impl CocoGitto {
                 hooks_config,
                 Some(&bump.package_name),
                 Some(package),
+                Some(&package_changelog),
             )?;
         }
 
         // Run global post hooks
-        self.run_hooks(
+        self.run_global_monorepo_hooks(
             HookType::PostBump,
             current.as_ref(),
             &next_version,
             hooks_config,
-            None,
-            None,
+            Some(&changelog),
+            &bumped_packages,
         )?;
 
         Ok(())
     }
 
     fn create_monorepo_version_manual(
-        &mut self,
+        &self,
         increment: IncrementCommand,
         pre_release: Option<&str>,
         hooks_config: Option<&str>,
@@ -199,6 +338,8 @@ impl CocoGitto {
         self.pre_bump_checks()?;
         // Get package bumps
         let bumps = self.get_current_packages()?;
+        // Packages are not bumped along with a manual global version
+        let bumped_packages: Vec<BumpedPackage> = vec![];
 
         // Get current global tag
         let old = self.repository.get_latest_tag();
@@ -210,9 +351,19 @@ impl CocoGitto {
             tag.version.pre = Prerelease::new(pre_release)?;
         }
 
-        let tag = Tag::create(tag.version, None);
+        let tag = Tag::create(tag.version, None, self.settings());
 
         if dry_run {
+            let current = self.repository.get_latest_tag().map(HookVersion::new).ok();
+            let next_version = HookVersion::new(tag.clone());
+            self.dry_run_hooks(
+                current.as_ref(),
+                &next_version,
+                hooks_config,
+                None,
+                None,
+                Some(&bumped_packages),
+            )?;
             print!("{}", tag);
             return Ok(());
         }
@@ -233,49 +384,97 @@ impl CocoGitto {
 
         changelog.pretty_print_bump_summary()?;
 
-        let path = settings::changelog_path();
-        let template = SETTINGS.get_monorepo_changelog_template()?;
+        let path = &self
+            .repository
+            .resolve_path(&self.settings().changelog.path);
+        let template = self.settings().get_monorepo_changelog_template()?;
+
+        let current = self.repository.get_latest_tag().map(HookVersion::new).ok();
+        let next_version = HookVersion::new(tag.clone());
 
-        changelog.write_to_file(
+        let hook_result = self.run_global_monorepo_hooks(
+            HookType::PreChangelog,
+            current.as_ref(),
+            &next_version,
+            hooks_config,
+            None,
+            &bumped_packages,
+        );
+
+        self.repository.add_all()?;
+        self.unwrap_or_stash_and_exit(&tag, hook_result);
+
+        let changelog = changelog.write_to_file(
             path,
             template,
             ReleaseType::MonoRepo(MonoRepoContext {
                 package_lock: true,
                 packages: template_context,
             }),
+            self.settings(),
         )?;
 
-        let current = self.repository.get_latest_tag().map(HookVersion::new).ok();
-        let next_version = HookVersion::new(tag.clone());
+        let hook_result = self
+            .run_global_monorepo_hooks(
+                HookType::PostChangelog,
+                current.as_ref(),
+                &next_version,
+                hooks_config,
+                Some(&changelog),
+                &bumped_packages,
+            )
+            .and_then(|_| {
+                self.run_global_monorepo_hooks(
+                    HookType::PreBump,
+                    current.as_ref(),
+                    &next_version,
+                    hooks_config,
+                    Some(&changelog),
+                    &bumped_packages,
+                )
+            });
+
+        self.repository.add_all()?;
+        self.unwrap_or_stash_and_exit(&tag, hook_result);
 
-        let hook_result = self.run_hooks(
-            HookType::PreBump,
+        let hook_result = self.run_global_monorepo_hooks(
+            HookType::PreCommit,
             current.as_ref(),
             &next_version,
             hooks_config,
-            None,
-            None,
+            Some(&changelog),
+            &bumped_packages,
         );
 
         self.repository.add_all()?;
         self.unwrap_or_stash_and_exit(&tag, hook_result);
 
         let sign = self.repository.gpg_sign();
-        self.repository.commit(
+        let oid = self.repository.commit(
             &format!("chore(version): {}", next_version.prefixed_tag),
             sign,
         )?;
 
+        let hook_result = self.run_global_monorepo_hooks(
+            HookType::PreTag,
+            current.as_ref(),
+            &next_version,
+            hooks_config,
+            Some(&changelog),
+            &bumped_packages,
+        );
+
+        self.unwrap_or_reset_and_exit(oid, &tag, hook_result);
         self.repository.create_tag(&tag)?;
 
         // Run global post hooks
-        self.run_hooks(
+        self.run_global_monorepo_hooks(
             HookType::PostBump,
             current.as_ref(),
             &next_version,
             hooks_config,
-            None,
-            None,
+            Some(&changelog),
+            &bumped_packages,
         )?;
 
         Ok(())
@@ -283,7 +482,7 @@ impl CocoGitto {
 
     fn get_current_packages(&self) -> Result<Vec<PackageData>> {
         let mut packages = vec![];
-        for (package_name, package) in SETTINGS.packages.iter() {
+        for (package_name, package) in self.settings().packages.iter() {
             let tag = self.repository.get_latest_package_tag(package_name);
             let tag = tag_or_fallback_to_zero(tag)?;
             packages.push(PackageData {
@@ -299,7 +498,7 @@ impl CocoGitto {
     // Calculate all package bump
     fn get_packages_bumps(&self, pre_release: Option<&str>) -> Result<Vec<PackageBumpData>> {
         let mut package_bumps = vec![];
-        for (package_name, package) in SETTINGS.packages.iter() {
+        for (package_name, package) in self.settings().packages.iter() {
             let old = self.repository.get_latest_package_tag(package_name);
             let old = tag_or_fallback_to_zero(old)?;
 
@@ -318,7 +517,11 @@ impl CocoGitto {
                 next_version.version.pre = Prerelease::new(pre_release)?;
             }
 
-            let tag = Tag::create(next_version.version, Some(package_name.to_string()));
+            let tag = Tag::create(
+                next_version.version,
+                Some(package_name.to_string()),
+                self.settings(),
+            );
             let increment = tag.get_increment_from(&old);
 
             if let Some(increment) = increment {
@@ -342,13 +545,15 @@ impl CocoGitto {
         Ok(package_bumps)
     }
 
-    // Run pre hooks and generate changelog for each package and git add the generated content
+    // Run pre hooks and generate changelog for each package and git add the generated content,
+    // returns the rendered package releases
     fn bump_packages(
-        &mut self,
+        &self,
         pre_release: Option<&str>,
         hooks_config: Option<&str>,
         package_bumps: &Vec<PackageBumpData>,
-    ) -> Result<()> {
+    ) -> Result<Vec<String>> {
+        let mut changelogs = vec![];
         for bump in package_bumps {
             let package_name = &bump.package_name;
             let old = self.repository.get_latest_package_tag(package_name);
@@ -371,10 +576,15 @@ impl CocoGitto {
                 next_version.version.pre = Prerelease::new(pre_release)?;
             }
 
-            let tag = Tag::create(next_version.version, Some(package_name.to_string()));
+            let tag = Tag::create(
+                next_version.version,
+                Some(package_name.to_string()),
+                self.settings(),
+            );
             let pattern = self.get_revspec_for_tag(&old)?;
 
-            let package = SETTINGS
+            let package = self
+                .settings()
                 .packages
                 .get(package_name.as_str())
                 .expect("package exists");
@@ -387,16 +597,13 @@ impl CocoGitto {
 
             changelog.pretty_print_bump_summary()?;
 
-            let path = package.changelog_path();
-            let template = SETTINGS.get_package_changelog_template()?;
+            let path = self.repository.resolve_path(&package.changelog_path());
+            let template = self.settings().get_package_changelog_template()?;
 
             let additional_context = ReleaseType::Package(PackageContext {
                 package_name: package_name.as_ref(),
             });
 
-            changelog.write_to_file(&path, template, additional_context)?;
-            info!("\tChangelog updated {:?}", path);
-
             let old_version = self
                 .repository
                 .get_latest_package_tag(package_name)
@@ -406,18 +613,49 @@ impl CocoGitto {
             let new_version = HookVersion::new(tag.clone());
 
             let hook_result = self.run_hooks(
-                HookType::PreBump,
+                HookType::PreChangelog,
                 old_version.as_ref(),
                 &new_version,
                 hooks_config,
                 Some(package_name),
                 Some(package),
+                None,
             );
 
             self.repository.add_all()?;
             self.unwrap_or_stash_and_exit(&tag, hook_result);
+
+            let changelog =
+                changelog.write_to_file(&path, template, additional_context, self.settings())?;
+            info!("\tChangelog updated {:?}", path);
+
+            let hook_result = self
+                .run_hooks(
+                    HookType::PostChangelog,
+                    old_version.as_ref(),
+                    &new_version,
+                    hooks_config,
+                    Some(package_name),
+                    Some(package),
+                    Some(&changelog),
+                )
+                .and_then(|_| {
+                    self.run_hooks(
+                        HookType::PreBump,
+                        old_version.as_ref(),
+                        &new_version,
+                        hooks_config,
+                        Some(package_name),
+                        Some(package),
+                        Some(&changelog),
+                    )
+                });
+
+            self.repository.add_all()?;
+            self.unwrap_or_stash_and_exit(&tag, hook_result);
+            changelogs.push(changelog);
         }
 
-        Ok(())
+        Ok(changelogs)
     }
 }
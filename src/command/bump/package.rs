@@ -5,7 +5,7 @@ use crate::conventional::version::IncrementCommand;
 use crate::git::tag::Tag;
 use crate::hook::HookVersion;
 use crate::settings::{HookType, MonoRepoPackage};
-use crate::{CocoGitto, SETTINGS};
+use crate::CocoGitto;
 use anyhow::Result;
 use colored::*;
 use log::info;
@@ -13,7 +13,7 @@ use semver::Prerelease;
 
 impl CocoGitto {
     pub fn create_package_version(
-        &mut self,
+        &self,
         (package_name, package): (&str, &MonoRepoPackage),
         increment: IncrementCommand,
         pre_release: Option<&str>,
@@ -30,9 +30,27 @@ impl CocoGitto {
             next_version.version.pre = Prerelease::new(pre_release)?;
         }
 
-        let tag = Tag::create(next_version.version.clone(), Some(package_name.to_string()));
+        let tag = Tag::create(
+            next_version.version.clone(),
+            Some(package_name.to_string()),
+            self.settings(),
+        );
 
         if dry_run {
+            let current = self
+                .repository
+                .get_latest_package_tag(package_name)
+                .map(HookVersion::new)
+                .ok();
+            let next_version = HookVersion::new(tag.clone());
+            self.dry_run_hooks(
+                current.as_ref(),
+                &next_version,
+                hooks_config,
+                Some(package_name),
+                Some(package),
+                None,
+            )?;
             print!("{}", tag);
             return Ok(());
         }
@@ -44,11 +62,9 @@ impl CocoGitto {
 
         changelog.pretty_print_bump_summary()?;
 
-        let path = package.changelog_path();
-        let template = SETTINGS.get_package_changelog_template()?;
+        let path = self.repository.resolve_path(&package.changelog_path());
+        let template = self.settings().get_package_changelog_template()?;
         let additional_context = ReleaseType::Package(PackageContext { package_name });
-        changelog.write_to_file(path, template, additional_context)?;
-
         let current = self
             .repository
             .get_latest_package_tag(package_name)
@@ -58,24 +74,79 @@ impl CocoGitto {
         let next_version = HookVersion::new(Tag::create(
             next_version.version,
             Some(package_name.to_string()),
+            self.settings(),
         ));
 
         let hook_result = self.run_hooks(
-            HookType::PreBump,
+            HookType::PreChangelog,
+            current.as_ref(),
+            &next_version,
+            hooks_config,
+            Some(package_name),
+            Some(package),
+            None,
+        );
+
+        self.repository.add_all()?;
+        self.unwrap_or_stash_and_exit(&tag, hook_result);
+
+        let changelog =
+            changelog.write_to_file(path, template, additional_context, self.settings())?;
+
+        let hook_result = self
+            .run_hooks(
+                HookType::PostChangelog,
+                current.as_ref(),
+                &next_version,
+                hooks_config,
+                Some(package_name),
+                Some(package),
+                Some(&changelog),
+            )
+            .and_then(|_| {
+                self.run_hooks(
+                    HookType::PreBump,
+                    current.as_ref(),
+                    &next_version,
+                    hooks_config,
+                    Some(package_name),
+                    Some(package),
+                    Some(&changelog),
+                )
+            });
+
+        self.repository.add_all()?;
+        self.unwrap_or_stash_and_exit(&tag, hook_result);
+
+        let hook_result = self.run_hooks(
+            HookType::PreCommit,
             current.as_ref(),
             &next_version,
             hooks_config,
             Some(package_name),
             Some(package),
+            Some(&changelog),
         );
 
         self.repository.add_all()?;
         self.unwrap_or_stash_and_exit(&tag, hook_result);
 
         let sign = self.repository.gpg_sign();
-        self.repository
+        let oid = self
+            .repository
             .commit(&format!("chore(version): {}", tag), sign)?;
 
+        let hook_result = self.run_hooks(
+            HookType::PreTag,
+            current.as_ref(),
+            &next_version,
+            hooks_config,
+            Some(package_name),
+            Some(package),
+            Some(&changelog),
+        );
+
+        self.unwrap_or_reset_and_exit(oid, &tag, hook_result);
         self.repository.create_tag(&tag)?;
 
         self.run_hooks(
@@ -85,6 +156,7 @@ impl CocoGitto {
             hooks_config,
             Some(package_name),
             Some(package),
+            Some(&changelog),
         )?;
 
         let current = current
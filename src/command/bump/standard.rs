@@ -1,19 +1,90 @@
-use crate::command::bump::{ensure_tag_is_greater_than_previous, tag_or_fallback_to_zero};
+use crate::command::bump::{
+    ensure_tag_is_greater_than_previous, exit_on_hook_failure, tag_or_fallback_to_zero,
+};
 
+use crate::conventional::changelog::release::Release;
 use crate::conventional::changelog::ReleaseType;
 use crate::conventional::version::IncrementCommand;
+use crate::error::CogError;
 use crate::git::tag::Tag;
 use crate::hook::HookVersion;
 use crate::settings::HookType;
-use crate::{settings, CocoGitto, SETTINGS};
+use crate::CocoGitto;
 use anyhow::Result;
 use colored::*;
+use git2::Oid;
 use log::info;
 use semver::Prerelease;
 
+/// A version bump computed by [`CocoGitto::plan_bump`], nothing is written to the repository.
+#[derive(Debug, Clone)]
+pub struct BumpPlan {
+    /// The latest version, `None` if the repository has no version tag yet
+    pub current: Option<Tag>,
+    pub next: Tag,
+    /// The changelog section of the next version
+    pub changelog: String,
+}
+
+/// A version bump applied by [`CocoGitto::apply_bump`].
+#[derive(Debug, Clone)]
+pub struct AppliedBump {
+    pub tag: Tag,
+    /// The `chore(version)` commit
+    pub commit: Oid,
+    /// The changelog section written for the new version
+    pub changelog: String,
+}
+
 impl CocoGitto {
+    /// Compute the next version and its changelog without modifying the repository
+    /// nor running any hook.
+    pub fn plan_bump(
+        &self,
+        increment: IncrementCommand,
+        pre_release: Option<&str>,
+    ) -> Result<BumpPlan, CogError> {
+        let (current_tag, tag) = self.next_version(increment, pre_release)?;
+        let pattern = self.get_revspec_for_tag(&current_tag)?;
+        let release = self.get_changelog_with_target_version(pattern, tag.clone())?;
+        let template = self.settings().get_changelog_template()?;
+        let changelog = release.into_markdown(template, self.settings())?;
+
+        Ok(BumpPlan {
+            current: (!current_tag.is_zero()).then_some(current_tag),
+            next: tag,
+            changelog,
+        })
+    }
+
+    /// Apply a [`BumpPlan`]: write the changelog, run the bump hooks, commit and tag the new version.
+    ///
+    /// If a hook fails before the version commit, the changes are stashed and
+    /// [`CogError::HookFailed`] is returned.
+    pub fn apply_bump(
+        &self,
+        plan: &BumpPlan,
+        hooks_config: Option<&str>,
+    ) -> Result<AppliedBump, CogError> {
+        self.pre_bump_checks()?;
+
+        // The repository may have been tagged since the plan was made
+        let current_tag = tag_or_fallback_to_zero(self.repository.get_latest_tag())?;
+        ensure_tag_is_greater_than_previous(&current_tag, &plan.next)?;
+
+        let pattern = self.get_revspec_for_tag(&current_tag)?;
+        let release = self.get_changelog_with_target_version(pattern, plan.next.clone())?;
+        let (commit, changelog) = self.release_version(release, &plan.next, hooks_config)?;
+
+        Ok(AppliedBump {
+            tag: plan.next.clone(),
+            commit,
+            changelog,
+        })
+    }
+
     pub fn create_version(
-        &mut self,
+        &self,
         increment: IncrementCommand,
         pre_release: Option<&str>,
         hooks_config: Option<&str>,
@@ -21,6 +92,46 @@ impl CocoGitto {
     ) -> Result<()> {
         self.pre_bump_checks()?;
 
+        let (current_tag, tag) = self.next_version(increment, pre_release)?;
+
+        if dry_run {
+            let current = self.repository.get_latest_tag().map(HookVersion::new).ok();
+            let next_version = HookVersion::new(tag.clone());
+            self.dry_run_hooks(
+                current.as_ref(),
+                &next_version,
+                hooks_config,
+                None,
+                None,
+                None,
+            )?;
+            print!("{}", tag);
+            return Ok(());
+        }
+
+        let pattern = self.get_revspec_for_tag(&current_tag)?;
+        let changelog = self.get_changelog_with_target_version(pattern, tag.clone())?;
+        changelog.pretty_print_bump_summary()?;
+
+        let current = self.repository.get_latest_tag().map(HookVersion::new).ok();
+        exit_on_hook_failure(self.release_version(changelog, &tag, hooks_config))?;
+
+        let current = current
+            .map(|current| current.prefixed_tag.to_string())
+            .unwrap_or_else(|| "...".to_string());
+        let next_version = HookVersion::new(tag);
+        let bump = format!("{} -> {}", current, next_version.prefixed_tag).green();
+        info!("Bumped version: {}", bump);
+
+        Ok(())
+    }
+
+    /// Get the latest version, falling back to `0.0.0`, and the version it is bumped to.
+    pub(crate) fn next_version(
+        &self,
+        increment: IncrementCommand,
+        pre_release: Option<&str>,
+    ) -> Result<(Tag, Tag), CogError> {
         let current_tag = self.repository.get_latest_tag();
         let current_tag = tag_or_fallback_to_zero(current_tag)?;
         let mut tag = current_tag.bump(increment, &self.repository)?;
@@ -31,46 +142,100 @@ impl CocoGitto {
             tag.version.pre = Prerelease::new(pre_release)?;
         }
 
-        let tag = Tag::create(tag.version, None);
+        let tag = Tag::create(tag.version, None, self.settings());
+        Ok((current_tag, tag))
+    }
 
-        if dry_run {
-            print!("{}", tag);
-            return Ok(());
-        }
+    /// Write the release to the changelog, run the bump hooks, commit and tag the new version.
+    /// Returns the version commit and the changelog content.
+    pub(crate) fn release_version(
+        &self,
+        release: Release<'_>,
+        tag: &Tag,
+        hooks_config: Option<&str>,
+    ) -> Result<(Oid, String), CogError> {
+        let path = &self
+            .repository
+            .resolve_path(&self.settings().changelog.path);
+        let template = self.settings().get_changelog_template()?;
 
-        let pattern = self.get_revspec_for_tag(&current_tag)?;
-        let changelog = self.get_changelog_with_target_version(pattern, tag.clone())?;
-        changelog.pretty_print_bump_summary()?;
+        let current = self.repository.get_latest_tag().map(HookVersion::new).ok();
 
-        let path = settings::changelog_path();
-        let template = SETTINGS.get_changelog_template()?;
+        let next_version = HookVersion::new(tag.clone());
 
-        changelog.write_to_file(path, template, ReleaseType::Standard)?;
+        let hook_result = self.run_hooks(
+            HookType::PreChangelog,
+            current.as_ref(),
+            &next_version,
+            hooks_config,
+            None,
+            None,
+            None,
+        );
 
-        let current = self.repository.get_latest_tag().map(HookVersion::new).ok();
+        self.repository.add_all()?;
+        self.stash_on_failure(tag, hook_result)?;
+
+        let changelog =
+            release.write_to_file(path, template, ReleaseType::Standard, self.settings())?;
+
+        let hook_result = self
+            .run_hooks(
+                HookType::PostChangelog,
+                current.as_ref(),
+                &next_version,
+                hooks_config,
+                None,
+                None,
+                Some(&changelog),
+            )
+            .and_then(|_| {
+                self.run_hooks(
+                    HookType::PreBump,
+                    current.as_ref(),
+                    &next_version,
+                    hooks_config,
+                    None,
+                    None,
+                    Some(&changelog),
+                )
+            });
 
-        let next_version = HookVersion::new(tag.clone());
+        self.repository.add_all()?;
+        self.stash_on_failure(tag, hook_result)?;
 
         let hook_result = self.run_hooks(
-            HookType::PreBump,
+            HookType::PreCommit,
             current.as_ref(),
             &next_version,
             hooks_config,
             None,
             None,
+            Some(&changelog),
         );
 
         self.repository.add_all()?;
-        self.unwrap_or_stash_and_exit(&tag, hook_result);
+        self.stash_on_failure(tag, hook_result)?;
 
         let sign = self.repository.gpg_sign();
 
-        self.repository.commit(
+        let oid = self.repository.commit(
             &format!("chore(version): {}", next_version.prefixed_tag),
             sign,
         )?;
 
-        self.repository.create_tag(&tag)?;
+        let hook_result = self.run_hooks(
+            HookType::PreTag,
+            current.as_ref(),
+            &next_version,
+            hooks_config,
+            None,
+            None,
+            Some(&changelog),
+        );
+
+        self.reset_on_failure(oid, tag, hook_result)?;
+        self.repository.create_tag(tag)?;
 
         self.run_hooks(
             HookType::PostBump,
@@ -79,14 +244,9 @@ impl CocoGitto {
             hooks_config,
             None,
             None,
+            Some(&changelog),
         )?;
 
-        let current = current
-            .map(|current| current.prefixed_tag.to_string())
-            .unwrap_or_else(|| "...".to_string());
-        let bump = format!("{} -> {}", current, next_version.prefixed_tag).green();
-        info!("Bumped version: {}", bump);
-
-        Ok(())
+        Ok((oid, changelog))
     }
 }
@@ -1,9 +1,20 @@
+use std::path::PathBuf;
+
 use crate::conventional::changelog::release::Release;
-use crate::conventional::changelog::template::Template;
+use crate::conventional::changelog::template::{
+    MonoRepoContext, PackageBumpContext, PackageContext, Template,
+};
+use crate::conventional::changelog::{replace_release, write_releases, ReleaseType};
+use crate::error::CogError;
+use crate::git::error::TagError;
+use crate::git::oid::OidOf;
 use crate::git::revspec::RevspecPattern;
+use crate::git::tag::Tag;
 use crate::CocoGitto;
 use anyhow::anyhow;
 use anyhow::Result;
+use itertools::Itertools;
+use log::info;
 
 impl CocoGitto {
     /// ## Get a changelog between two oids
@@ -21,17 +32,215 @@ impl CocoGitto {
         } else {
             let commit_range = self.repository.get_commit_range(&pattern)?;
 
-            Ok(Release::from(commit_range))
+            Ok(Release::from_commit_range(commit_range, self.settings()))
         }
     }
 
+    /// Render the releases in `pattern` with the configured changelog template.
+    pub fn changelog(&self, pattern: RevspecPattern) -> Result<String, CogError> {
+        let release = self.repository.get_release_range(pattern)?;
+        let template = self.settings().get_changelog_template()?;
+
+        Ok(release.into_markdown(template, self.settings())?)
+    }
+
     pub fn get_changelog_at_tag(&self, tag: &str, template: Template) -> Result<String> {
         let pattern = format!("..{}", tag);
         let pattern = RevspecPattern::from(pattern.as_str());
         let changelog = self.get_changelog(pattern, false)?;
 
         changelog
-            .into_markdown(template)
+            .into_markdown(template, self.settings())
             .map_err(|err| anyhow!(err))
     }
+
+    /// Render every version of the repository changelog, newest first, as written by
+    /// [`CocoGitto::write_full_changelog`].
+    /// Monorepo global versions list the package versions released alongside them.
+    pub fn get_full_changelog(&self) -> Result<String, CogError> {
+        self.join_releases(self.render_versions(None)?)
+            .ok_or(CogError::Tag(TagError::NoTag))
+    }
+
+    /// Render every version of a monorepo package changelog, newest first.
+    /// Returns `None` if the package was never released.
+    pub fn get_full_package_changelog(
+        &self,
+        package_name: &str,
+    ) -> Result<Option<String>, CogError> {
+        Ok(self.join_releases(self.render_versions(Some(package_name))?))
+    }
+
+    /// Join rendered releases with the changelog separator, `None` if there are none.
+    fn join_releases(&self, releases: Vec<String>) -> Option<String> {
+        if releases.is_empty() {
+            None
+        } else {
+            let separator = format!("\n{}\n\n", self.settings().changelog.separator());
+            Some(releases.join(&separator))
+        }
+    }
+
+    /// Render the versions of a package, or the global versions if `None`, newest first.
+    fn render_versions(&self, package_name: Option<&str>) -> Result<Vec<String>, CogError> {
+        let tags = self.repository.all_tags()?;
+        let mut releases = vec![];
+
+        for tag in tags
+            .iter()
+            .filter(|tag| tag.package.as_deref() == package_name)
+            .sorted()
+            .rev()
+        {
+            let (_, release) = self.render_version(&tags, tag)?;
+            releases.push(release);
+        }
+
+        Ok(releases)
+    }
+
+    /// Render the changelog section of a version tag, along with the path of its changelog.
+    fn render_version(&self, tags: &[Tag], tag: &Tag) -> Result<(PathBuf, String), CogError> {
+        let settings = self.settings();
+        let first_commit = self.repository.get_first_commit()?;
+        let previous = tags
+            .iter()
+            .filter(|other| other.package == tag.package && *other < tag)
+            .max();
+        let from = previous
+            .map(Tag::to_string)
+            .unwrap_or_else(|| first_commit.to_string());
+        let pattern = RevspecPattern::from((from.as_str(), tag.to_string().as_str()));
+
+        match &tag.package {
+            Some(package_name) => {
+                // Package tags are only parsed for the configured packages
+                let package = settings
+                    .packages
+                    .get(package_name)
+                    .expect("package tags belong to a configured package");
+                let range = self
+                    .repository
+                    .get_commit_range_for_package(&pattern, package_name)?;
+                let release = Release::from_commit_range(range, settings);
+                let template = settings.get_package_changelog_template()?;
+                let kind = ReleaseType::Package(PackageContext { package_name });
+                let release = release.render(template, kind, settings)?;
+                Ok((package.changelog_path(), release))
+            }
+            None if settings.packages.is_empty() => {
+                let range = self.repository.get_commit_range(&pattern)?;
+                let release = Release::from_commit_range(range, settings);
+                let template = settings.get_changelog_template()?;
+                let release = release.render(template, ReleaseType::Standard, settings)?;
+                Ok((settings.changelog.path.clone(), release))
+            }
+            None => {
+                let range = self
+                    .repository
+                    .get_commit_range_for_monorepo_global(&pattern)?;
+                let release = Release::from_commit_range(range, settings);
+
+                // Package tags are created on the global version commit
+                let bumped: Vec<(&str, String, &Tag, Option<&Tag>)> = settings
+                    .packages
+                    .iter()
+                    .sorted_by(|(a, _), (b, _)| a.cmp(b))
+                    .filter_map(|(name, package)| {
+                        let package_tags: Vec<&Tag> = tags
+                            .iter()
+                            .filter(|package_tag| package_tag.package.as_ref() == Some(name))
+                            .sorted()
+                            .collect();
+                        let position = package_tags
+                            .iter()
+                            .position(|package_tag| package_tag.oid == tag.oid)?;
+                        let previous = position
+                            .checked_sub(1)
+                            .map(|position| package_tags[position]);
+                        let path = package.path.to_string_lossy().to_string();
+                        Some((name.as_str(), path, package_tags[position], previous))
+                    })
+                    .collect();
+
+                let packages = bumped
+                    .iter()
+                    .map(|(name, path, version, previous)| PackageBumpContext {
+                        package_name: name,
+                        package_path: path,
+                        version: OidOf::Tag((*version).clone()),
+                        from: Some(
+                            previous
+                                .map(|previous| OidOf::Tag(previous.clone()))
+                                .unwrap_or(OidOf::Other(first_commit)),
+                        ),
+                    })
+                    .collect();
+
+                let template = settings.get_monorepo_changelog_template()?;
+                let kind = ReleaseType::MonoRepo(MonoRepoContext {
+                    package_lock: false,
+                    packages,
+                });
+
+                let release = release.render(template, kind, settings)?;
+                Ok((settings.changelog.path.clone(), release))
+            }
+        }
+    }
+
+    /// Re-render the changelog section of the version `tag` and replace it in place,
+    /// the other sections of the changelog are left untouched. The section keeps the date
+    /// of the tagged commit. Returns the changelog path.
+    pub fn rewrite_changelog_version(&self, tag: &str) -> Result<PathBuf, CogError> {
+        let tags = self.repository.all_tags()?;
+        let tag = self.repository.resolve_tag(tag)?;
+        let (path, release) = self.render_version(&tags, &tag)?;
+
+        let changelog_path = self.repository.resolve_path(&path);
+        replace_release(&changelog_path, &tag.to_string(), &release, self.settings())?;
+        info!("Changelog section {} rewritten in {:?}", tag, path);
+
+        Ok(path)
+    }
+
+    /// Regenerate the changelog from every version tag, along with the package changelogs
+    /// of a monorepo, keeping the existing headers and footers.
+    /// Returns the written changelog paths.
+    pub fn write_full_changelog(&self) -> Result<Vec<PathBuf>, CogError> {
+        let settings = self.settings();
+        let has_version = self.repository.get_latest_tag().is_ok();
+        if !has_version && settings.packages.is_empty() {
+            return Err(TagError::NoTag.into());
+        }
+
+        let mut written = vec![];
+
+        if has_version {
+            let releases = self.render_versions(None)?;
+            let path = self.repository.resolve_path(&settings.changelog.path);
+            write_releases(&path, &releases, settings, None)?;
+            written.push(settings.changelog.path.clone());
+        }
+
+        for (name, package) in settings
+            .packages
+            .iter()
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        {
+            let releases = self.render_versions(Some(name))?;
+            if !releases.is_empty() {
+                let path = package.changelog_path();
+                let changelog_path = self.repository.resolve_path(&path);
+                write_releases(&changelog_path, &releases, settings, Some(name))?;
+                written.push(path);
+            }
+        }
+
+        for path in &written {
+            info!("Changelog written to {:?}", path);
+        }
+
+        Ok(written)
+    }
 }
@@ -1,5 +1,7 @@
 use crate::conventional::commit::Commit;
-use crate::error::CogCheckReport;
+use crate::conventional::error::ConventionalCommitError;
+use crate::error::{CogCheckReport, CogError};
+use crate::git::oid::OidOf;
 use crate::git::revspec::RevspecPattern;
 use crate::CocoGitto;
 use anyhow::anyhow;
@@ -7,42 +9,76 @@ use anyhow::Result;
 use colored::*;
 use log::info;
 
-impl CocoGitto {
-    pub fn check(&self, check_from_latest_tag: bool, ignore_merge_commits: bool) -> Result<()> {
-        let commit_range = if check_from_latest_tag {
-            self.repository
-                .get_commit_range(&RevspecPattern::default())?
-        } else {
-            self.repository.all_commits()?
-        };
+/// The outcome of checking a range of commits against the conventional commit specification.
+#[derive(Debug)]
+pub struct CheckOutcome {
+    pub from: OidOf,
+    pub to: OidOf,
+    /// The number of commits checked
+    pub checked: usize,
+    /// The non compliant commits
+    pub errors: Vec<ConventionalCommitError>,
+}
 
-        let errors: Vec<_> = if ignore_merge_commits {
-            commit_range
-                .commits
-                .iter()
-                .filter(|commit| commit.parent_count() <= 1)
-                .map(Commit::from_git_commit)
-                .filter_map(Result::err)
-                .collect()
-        } else {
-            commit_range
-                .commits
-                .iter()
-                .map(Commit::from_git_commit)
-                .filter_map(Result::err)
-                .collect()
-        };
+impl CheckOutcome {
+    pub fn is_compliant(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
 
-        if errors.is_empty() {
+impl CocoGitto {
+    pub fn check(
+        &self,
+        check_from_latest_tag: bool,
+        ignore_merge_commits: bool,
+        range: Option<RevspecPattern>,
+    ) -> Result<()> {
+        let range = range.or_else(|| check_from_latest_tag.then(RevspecPattern::default));
+        let outcome = self.check_commits(range, ignore_merge_commits)?;
+
+        if outcome.is_compliant() {
             let msg = "No errored commits".green();
             info!("{}", msg);
             Ok(())
         } else {
             let report = CogCheckReport {
-                from: commit_range.from,
-                errors: errors.into_iter().map(|err| *err).collect(),
+                from: outcome.from,
+                to: outcome.to,
+                errors: outcome.errors,
             };
             Err(anyhow!("{}", report))
         }
     }
+
+    /// Check the commits in `range`, or the whole history if `None`, without reporting anything.
+    pub fn check_commits(
+        &self,
+        range: Option<RevspecPattern>,
+        ignore_merge_commits: bool,
+    ) -> Result<CheckOutcome, CogError> {
+        let commit_range = match range {
+            Some(range) => self.repository.get_commit_range(&range)?,
+            None => self.repository.all_commits()?,
+        };
+
+        let commits: Vec<_> = commit_range
+            .commits
+            .iter()
+            .filter(|commit| !ignore_merge_commits || commit.parent_count() <= 1)
+            .collect();
+
+        let errors = commits
+            .iter()
+            .map(|commit| Commit::from_git_commit(commit, self.settings()))
+            .filter_map(Result::err)
+            .map(|err| *err)
+            .collect();
+
+        Ok(CheckOutcome {
+            from: commit_range.from,
+            to: commit_range.to,
+            checked: commits.len(),
+            errors,
+        })
+    }
 }
@@ -45,7 +45,7 @@ impl CocoGitto {
 
         // Pretty print a conventional commit summary
         let commit = self.repository.0.find_commit(oid)?;
-        let commit = Commit::from_git_commit(&commit)?;
+        let commit = Commit::from_git_commit(&commit, self.settings())?;
         info!("{}", commit);
 
         Ok(())
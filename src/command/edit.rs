@@ -1,6 +1,6 @@
 use crate::conventional::commit::{verify, Commit};
 use crate::git::revspec::RevspecPattern;
-use crate::{CocoGitto, SETTINGS};
+use crate::CocoGitto;
 use anyhow::{anyhow, Result};
 use colored::*;
 use git2::{Oid, RebaseOptions};
@@ -28,7 +28,7 @@ impl CocoGitto {
             .commits
             .iter()
             .map(|commit| {
-                let conv_commit = Commit::from_git_commit(commit);
+                let conv_commit = Commit::from_git_commit(commit, self.settings());
                 (commit.id(), conv_commit)
             })
             .filter(|commit| commit.1.is_err())
@@ -93,11 +93,12 @@ impl CocoGitto {
                             .collect();
 
                         rebase.commit(None, &original_commit.committer(), Some(&new_message))?;
-                        let ignore_merge_commit = SETTINGS.ignore_merge_commits;
+                        let ignore_merge_commit = self.settings().ignore_merge_commits;
                         match verify(
                             self.repository.get_author().ok(),
                             &new_message,
                             ignore_merge_commit,
+                            self.settings(),
                         ) {
                             Ok(_) => {
                                 info!("Changed commit message to:\"{}\"", &new_message.trim_end())
@@ -1,12 +1,138 @@
+use crate::conventional::changelog::template::parse_remote_url;
+use crate::git::hook::{ExistingHook, HookKind};
 use crate::git::repository::Repository;
-use crate::settings::Settings;
-use crate::CONFIG_PATH;
-use anyhow::anyhow;
-use log::info;
-use std::path::Path;
-use std::process::exit;
+use crate::settings::{MonoRepoPackage, Settings};
+use crate::{CocoGitto, CONFIG_PATH};
+use anyhow::{anyhow, bail, Context};
+use globset::GlobBuilder;
+use log::{info, warn};
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// The kind of project found in the initialized directory.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ProjectKind {
+    /// A `Cargo.toml` with a `[workspace]` section
+    CargoWorkspace,
+    /// A `package.json` with a `workspaces` field
+    NpmWorkspaces,
+    Plain,
+}
+
+impl Display for ProjectKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectKind::CargoWorkspace => write!(f, "Cargo workspace"),
+            ProjectKind::NpmWorkspaces => write!(f, "npm workspaces"),
+            ProjectKind::Plain => write!(f, "plain repository"),
+        }
+    }
+}
+
+/// A workspace member that can be defined as a monorepo package.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DetectedPackage {
+    /// The package name from its manifest, or its directory name
+    pub name: String,
+    /// The package path, relative to the initialized directory
+    pub path: PathBuf,
+}
+
+/// What `cog init` found in the initialized directory.
+#[derive(Debug, Clone)]
+pub struct Project {
+    pub kind: ProjectKind,
+    pub packages: Vec<DetectedPackage>,
+    /// The `origin` remote url, if it can be used as the changelog remote context
+    pub origin: Option<String>,
+}
+
+impl Project {
+    /// Detect the project kind, its workspace members and its `origin` remote.
+    pub fn detect<S: AsRef<Path> + ?Sized>(path: &S) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+
+        let (kind, packages) = if let Some(members) = cargo_workspace_members(path)? {
+            let packages = detect_packages(path, &members, "Cargo.toml", cargo_package_name);
+            (ProjectKind::CargoWorkspace, packages)
+        } else if let Some(members) = npm_workspaces(path)? {
+            let packages = detect_packages(path, &members, "package.json", npm_package_name);
+            (ProjectKind::NpmWorkspaces, packages)
+        } else {
+            (ProjectKind::Plain, vec![])
+        };
+
+        let origin = Repository::open(path)
+            .ok()
+            .and_then(|repository| repository.get_remote_url("origin"))
+            .filter(|url| parse_remote_url(url).is_some());
+
+        Ok(Project {
+            kind,
+            packages,
+            origin,
+        })
+    }
+}
+
+/// How `cog init` sets up the repository, the default writes the default settings.
+#[derive(Debug, Default, Clone)]
+pub struct InitOptions {
+    /// Define the detected workspace members as monorepo packages
+    pub packages: bool,
+    /// Set the changelog remote, owner and repository from the `origin` remote
+    pub remote: bool,
+    /// Changelog template, either a predefined one (`default`, `remote`, `full_hash`) or a path
+    pub changelog_template: Option<String>,
+    /// Git hooks to install once the repository is initialized
+    pub hooks: Vec<HookKind>,
+}
+
+impl InitOptions {
+    /// The settings written to `cog.toml` for the given project.
+    pub fn settings(&self, project: &Project) -> Settings {
+        let mut settings = Settings::default();
+
+        if self.packages {
+            settings.packages = project
+                .packages
+                .iter()
+                .map(|package| {
+                    let config = MonoRepoPackage {
+                        path: package.path.clone(),
+                        ..Default::default()
+                    };
+                    (package.name.clone(), config)
+                })
+                .collect();
+        }
+
+        if self.remote {
+            match project.origin.as_deref().and_then(parse_remote_url) {
+                Some((remote, owner, repository)) => {
+                    settings.changelog.remote = Some(remote);
+                    settings.changelog.owner = Some(owner);
+                    settings.changelog.repository = Some(repository);
+                }
+                None => warn!("No usable 'origin' remote found, skipping changelog remote setup"),
+            }
+        }
+
+        settings.changelog.template = self.changelog_template.clone();
+        settings
+    }
+}
 
 pub fn init<S: AsRef<Path> + ?Sized>(path: &S) -> anyhow::Result<()> {
+    init_with_options(path, &InitOptions::default())
+}
+
+/// Initialize a git repository if needed and write its `cog.toml` according to `options`.
+pub fn init_with_options<S: AsRef<Path> + ?Sized>(
+    path: &S,
+    options: &InitOptions,
+) -> anyhow::Result<()> {
     let path = path.as_ref();
 
     if !path.exists() {
@@ -29,30 +155,29 @@ pub fn init<S: AsRef<Path> + ?Sized>(path: &S) -> anyhow::Result<()> {
                 is_init_commit = true;
                 repo
             }
-            Err(err) => panic!("Unable to init repository on {:?}: {}", &path, err),
+            Err(err) => bail!("Unable to init repository on {:?}: {}", &path, err),
         },
     };
 
-    let settings = Settings::default();
     let settings_path = path.join(CONFIG_PATH);
     if settings_path.exists() {
-        eprint!("Found {} in {:?}, Nothing to do", CONFIG_PATH, &path);
-        exit(1);
-    } else {
-        std::fs::write(
-            &settings_path,
-            toml::to_string(&settings)
-                .map_err(|err| anyhow!("failed to serialize {}\n\ncause: {}", CONFIG_PATH, err))?,
-        )
-        .map_err(|err| {
-            anyhow!(
-                "failed to write file `{:?}`\n\ncause: {}",
-                settings_path,
-                err
-            )
-        })?;
+        bail!("Found {} in {:?}, Nothing to do", CONFIG_PATH, &path);
     }
 
+    let project = Project::detect(path)?;
+    let settings = options.settings(&project);
+
+    let content = toml::to_string(&settings)
+        .map_err(|err| anyhow!("failed to serialize {}\n\ncause: {}", CONFIG_PATH, err))?;
+
+    std::fs::write(&settings_path, content).map_err(|err| {
+        anyhow!(
+            "failed to write file `{:?}`\n\ncause: {}",
+            settings_path,
+            err
+        )
+    })?;
+
     repository.add_all()?;
 
     if is_init_commit {
@@ -60,5 +185,304 @@ pub fn init<S: AsRef<Path> + ?Sized>(path: &S) -> anyhow::Result<()> {
         repository.commit("chore: initial commit", sign)?;
     }
 
+    if !options.hooks.is_empty() {
+        let cocogitto = CocoGitto::with_settings(path, settings)?;
+        for kind in &options.hooks {
+            cocogitto.install_hook(*kind, ExistingHook::Refuse)?;
+        }
+    }
+
     Ok(())
 }
+
+/// The `[workspace]` members of `Cargo.toml`, without the `exclude`d ones.
+fn cargo_workspace_members(path: &Path) -> anyhow::Result<Option<Members>> {
+    let manifest_path = path.join("Cargo.toml");
+    if !manifest_path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&manifest_path)?;
+    let manifest: toml::Value =
+        toml::from_str(&content).with_context(|| format!("failed to parse {:?}", manifest_path))?;
+
+    let workspace = match manifest.get("workspace") {
+        Some(workspace) => workspace,
+        None => return Ok(None),
+    };
+
+    let strings = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(toml::Value::as_array)
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(toml::Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    Ok(Some(Members {
+        include: strings("members"),
+        exclude: strings("exclude"),
+    }))
+}
+
+/// The `workspaces` of `package.json`, either an array or a `packages` field.
+/// Patterns starting with `!` exclude members.
+fn npm_workspaces(path: &Path) -> anyhow::Result<Option<Members>> {
+    let manifest_path = path.join("package.json");
+    if !manifest_path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&manifest_path)?;
+    let manifest: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("failed to parse {:?}", manifest_path))?;
+
+    let workspaces = match manifest.get("workspaces") {
+        Some(serde_json::Value::Object(workspaces)) => workspaces.get("packages"),
+        workspaces => workspaces,
+    };
+
+    let patterns: Vec<&str> = match workspaces.and_then(serde_json::Value::as_array) {
+        Some(patterns) => patterns.iter().filter_map(|value| value.as_str()).collect(),
+        None => return Ok(None),
+    };
+
+    let (exclude, include): (Vec<&str>, Vec<&str>) = patterns
+        .into_iter()
+        .partition(|pattern| pattern.starts_with('!'));
+
+    Ok(Some(Members {
+        include: include.into_iter().map(str::to_string).collect(),
+        exclude: exclude
+            .into_iter()
+            .map(|pattern| pattern.trim_start_matches('!').to_string())
+            .collect(),
+    }))
+}
+
+struct Members {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+fn cargo_package_name(manifest: &str) -> Option<String> {
+    let manifest: toml::Value = toml::from_str(manifest).ok()?;
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn npm_package_name(manifest: &str) -> Option<String> {
+    let manifest: serde_json::Value = serde_json::from_str(manifest).ok()?;
+    manifest.get("name")?.as_str().map(str::to_string)
+}
+
+/// Expand the member patterns into the directories holding a `manifest` file.
+fn detect_packages(
+    root: &Path,
+    members: &Members,
+    manifest: &str,
+    package_name: fn(&str) -> Option<String>,
+) -> Vec<DetectedPackage> {
+    let matches = |patterns: &[String], path: &Path| {
+        patterns.iter().any(|pattern| {
+            GlobBuilder::new(pattern.trim_start_matches("./").trim_end_matches('/'))
+                .literal_separator(true)
+                .build()
+                .map(|glob| glob.compile_matcher().is_match(path))
+                .unwrap_or(false)
+        })
+    };
+
+    let depth = members
+        .include
+        .iter()
+        .map(|pattern| Path::new(pattern).components().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut packages: Vec<DetectedPackage> = subdirectories(root, Path::new(""), depth)
+        .into_iter()
+        .filter(|path| matches(&members.include, path) && !matches(&members.exclude, path))
+        .filter_map(|path| {
+            let content = fs::read_to_string(root.join(&path).join(manifest)).ok()?;
+            let name = package_name(&content).or_else(|| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+            })?;
+
+            Some(DetectedPackage { name, path })
+        })
+        .collect();
+
+    packages.sort_by(|a, b| a.path.cmp(&b.path));
+    packages
+}
+
+/// Every directory under `root` up to `depth`, relative to `root`.
+/// Hidden directories and build outputs are skipped.
+fn subdirectories(root: &Path, relative: &Path, depth: usize) -> Vec<PathBuf> {
+    if depth == 0 {
+        return vec![];
+    }
+
+    let entries = match fs::read_dir(root.join(relative)) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+
+    let mut directories = vec![];
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let is_dir = entry.file_type().map(|ty| ty.is_dir()).unwrap_or(false);
+        if !is_dir || name.starts_with('.') || name == "target" || name == "node_modules" {
+            continue;
+        }
+
+        let path: PathBuf = relative
+            .join(&name)
+            .components()
+            .filter(|component| !matches!(component, Component::CurDir))
+            .collect();
+
+        directories.extend(subdirectories(root, &path, depth - 1));
+        directories.push(path);
+    }
+
+    directories
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::init::{DetectedPackage, InitOptions, Project, ProjectKind};
+    use anyhow::Result;
+    use cmd_lib::run_cmd;
+    use sealed_test::prelude::*;
+    use speculoos::prelude::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[sealed_test]
+    fn detect_cargo_workspace() -> Result<()> {
+        // Arrange
+        fs::write(
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\", \"cli\"]\nexclude = [\"crates/skipped\"]\n",
+        )?;
+        for (dir, name) in [
+            ("crates/one", "one"),
+            ("crates/two", "two-lib"),
+            ("crates/skipped", "skipped"),
+            ("cli", "cli"),
+        ] {
+            fs::create_dir_all(dir)?;
+            fs::write(
+                PathBuf::from(dir).join("Cargo.toml"),
+                format!("[package]\nname = \"{name}\"\n"),
+            )?;
+        }
+
+        // Act
+        let project = Project::detect(".")?;
+
+        // Assert
+        assert_that!(project.kind).is_equal_to(ProjectKind::CargoWorkspace);
+        assert_that!(project.packages).is_equal_to(vec![
+            DetectedPackage {
+                name: "cli".to_string(),
+                path: PathBuf::from("cli"),
+            },
+            DetectedPackage {
+                name: "one".to_string(),
+                path: PathBuf::from("crates/one"),
+            },
+            DetectedPackage {
+                name: "two-lib".to_string(),
+                path: PathBuf::from("crates/two"),
+            },
+        ]);
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn detect_npm_workspaces() -> Result<()> {
+        // Arrange
+        fs::write(
+            "package.json",
+            r#"{ "workspaces": { "packages": ["packages/*", "!packages/private"] } }"#,
+        )?;
+        fs::create_dir_all("packages/web")?;
+        fs::write("packages/web/package.json", r#"{ "name": "@acme/web" }"#)?;
+        fs::create_dir_all("packages/private")?;
+        fs::write("packages/private/package.json", r#"{ "name": "private" }"#)?;
+
+        // Act
+        let project = Project::detect(".")?;
+
+        // Assert
+        assert_that!(project.kind).is_equal_to(ProjectKind::NpmWorkspaces);
+        assert_that!(project.packages).is_equal_to(vec![DetectedPackage {
+            name: "@acme/web".to_string(),
+            path: PathBuf::from("packages/web"),
+        }]);
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn detect_plain_repository_with_origin() -> Result<()> {
+        // Arrange
+        run_cmd!(
+            git init;
+            git remote add origin git@github.com:cocogitto/cocogitto.git;
+        )?;
+
+        // Act
+        let project = Project::detect(".")?;
+
+        // Assert
+        assert_that!(project.kind).is_equal_to(ProjectKind::Plain);
+        assert_that!(project.packages).is_empty();
+        assert_that!(project.origin)
+            .is_equal_to(Some("git@github.com:cocogitto/cocogitto.git".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn settings_from_options() {
+        // Arrange
+        let project = Project {
+            kind: ProjectKind::CargoWorkspace,
+            packages: vec![DetectedPackage {
+                name: "one".to_string(),
+                path: PathBuf::from("crates/one"),
+            }],
+            origin: Some("https://github.com/cocogitto/cocogitto.git".to_string()),
+        };
+
+        let options = InitOptions {
+            packages: true,
+            remote: true,
+            changelog_template: Some("remote".to_string()),
+            hooks: vec![],
+        };
+
+        // Act
+        let settings = options.settings(&project);
+
+        // Assert
+        assert_that!(settings.packages.get("one").map(|package| &package.path))
+            .is_equal_to(Some(&PathBuf::from("crates/one")));
+        assert_that!(settings.changelog.remote).is_equal_to(Some("github.com".to_string()));
+        assert_that!(settings.changelog.owner).is_equal_to(Some("cocogitto".to_string()));
+        assert_that!(settings.changelog.repository).is_equal_to(Some("cocogitto".to_string()));
+        assert_that!(settings.changelog.template).is_equal_to(Some("remote".to_string()));
+    }
+}
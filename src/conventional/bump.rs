@@ -1,20 +1,31 @@
+use crate::conventional::commit::drop_reverted_commits;
 use crate::conventional::error::BumpError;
 use crate::conventional::version::Increment;
-use crate::{Commit, IncrementCommand, Repository, RevspecPattern, Tag, SETTINGS};
+use crate::settings::Settings;
+use crate::{Commit, IncrementCommand, Repository, RevspecPattern, Tag};
 use conventional_commit_parser::commit::CommitType;
 use git2::Commit as Git2Commit;
-use once_cell::sync::Lazy;
 use semver::{BuildMetadata, Prerelease, Version};
 
-static FILTER_MERGE_COMMITS: Lazy<fn(&&git2::Commit) -> bool> = Lazy::new(|| {
-    |commit| {
-        if SETTINGS.ignore_merge_commits {
-            commit.parent_count() <= 1
-        } else {
-            true
-        }
-    }
-});
+fn is_ignored_merge_commit(commit: &Git2Commit, settings: &Settings) -> bool {
+    settings.ignore_merge_commits && commit.parent_count() > 1
+}
+
+/// The conventional commits of a bump range, without ignored merge commits
+/// and without commits reverted within the range.
+fn conventional_commits(commits: &[Git2Commit], settings: &Settings) -> Vec<Commit> {
+    let commits: Vec<&Git2Commit> = commits
+        .iter()
+        .filter(|commit| !is_ignored_merge_commit(commit, settings))
+        .collect();
+
+    drop_reverted_commits(commits)
+        .into_iter()
+        .map(|commit| Commit::from_squashed_git_commit(commit, settings))
+        .filter_map(Result::ok)
+        .flatten()
+        .collect()
+}
 
 pub(crate) trait Bump {
     fn manual_bump(&self, version: &str) -> Result<Self, semver::Error>
@@ -142,17 +153,7 @@ impl Tag {
         let pattern = RevspecPattern::from(pattern);
         let commits = repository.get_commit_range(&pattern)?;
 
-        let commits: Vec<&Git2Commit> = commits
-            .commits
-            .iter()
-            .filter(&*FILTER_MERGE_COMMITS)
-            .collect();
-
-        let conventional_commits: Vec<Commit> = commits
-            .iter()
-            .map(|commit| Commit::from_git_commit(commit))
-            .filter_map(Result::ok)
-            .collect();
+        let conventional_commits = conventional_commits(&commits.commits, repository.settings());
 
         let increment_type = self.version_increment_from_commit_history(&conventional_commits)?;
 
@@ -183,17 +184,7 @@ impl Tag {
         let pattern = pattern.as_str();
         let pattern = RevspecPattern::from(pattern);
         let commits = repository.get_commit_range_for_package(&pattern, package)?;
-        let commits: Vec<&Git2Commit> = commits
-            .commits
-            .iter()
-            .filter(&*FILTER_MERGE_COMMITS)
-            .collect();
-
-        let conventional_commits: Vec<Commit> = commits
-            .iter()
-            .map(|commit| Commit::from_git_commit(commit))
-            .filter_map(Result::ok)
-            .collect();
+        let conventional_commits = conventional_commits(&commits.commits, repository.settings());
 
         let increment_type = self.version_increment_from_commit_history(&conventional_commits)?;
 
@@ -223,17 +214,7 @@ impl Tag {
         let pattern = RevspecPattern::from(pattern);
         let commits = repository.get_commit_range_for_monorepo_global(&pattern)?;
 
-        let commits: Vec<&Git2Commit> = commits
-            .commits
-            .iter()
-            .filter(&*FILTER_MERGE_COMMITS)
-            .collect();
-
-        let conventional_commits: Vec<Commit> = commits
-            .iter()
-            .map(|commit| Commit::from_git_commit(commit))
-            .filter_map(Result::ok)
-            .collect();
+        let conventional_commits = conventional_commits(&commits.commits, repository.settings());
 
         let increment_type = self.version_increment_from_commit_history(&conventional_commits)?;
 
@@ -321,7 +302,7 @@ mod test {
     fn major_bump() -> Result<()> {
         // Arrange
         let repository = Repository::init(".")?;
-        let base_version = Tag::from_str("1.0.0", None)?;
+        let base_version = Tag::from_str("1.0.0", None, repository.settings())?;
 
         // Act
         let tag = base_version.bump(IncrementCommand::Major, &repository)?;
@@ -335,7 +316,7 @@ mod test {
     fn minor_bump() -> Result<()> {
         // Arrange
         let repository = Repository::init(".")?;
-        let base_version = Tag::from_str("1.0.0", None)?;
+        let base_version = Tag::from_str("1.0.0", None, repository.settings())?;
 
         // Act
         let tag = base_version.bump(IncrementCommand::Minor, &repository)?;
@@ -349,7 +330,7 @@ mod test {
     fn patch_bump() -> Result<()> {
         // Arrange
         let repository = Repository::init(".")?;
-        let base_version = Tag::from_str("1.0.0", None)?;
+        let base_version = Tag::from_str("1.0.0", None, repository.settings())?;
 
         // Act
         let tag = base_version.bump(IncrementCommand::Patch, &repository)?;
@@ -363,7 +344,7 @@ mod test {
     fn should_get_next_auto_version_patch() -> Result<()> {
         // Arrange
         let patch = Commit::commit_fixture(CommitType::BugFix, false);
-        let base_version = Tag::from_str("1.0.0", None)?;
+        let base_version = Tag::from_str("1.0.0", None, &Settings::default())?;
 
         // Act
         let increment = base_version.version_increment_from_commit_history(&[patch]);
@@ -380,7 +361,7 @@ mod test {
     fn increment_minor_version_should_set_patch_to_zero() -> Result<()> {
         // Arrange
         let repository = Repository::init(".")?;
-        let version = Tag::from_str("1.1.1", None)?;
+        let version = Tag::from_str("1.1.1", None, repository.settings())?;
 
         // Act
         let tag = version.bump(IncrementCommand::Minor, &repository)?;
@@ -395,7 +376,7 @@ mod test {
     fn increment_major_version_should_set_minor_and_patch_to_zero() -> Result<()> {
         // Arrange
         let repository = Repository::init(".")?;
-        let version = Tag::from_str("1.1.1", None)?;
+        let version = Tag::from_str("1.1.1", None, repository.settings())?;
 
         // Act
         let tag = version.bump(IncrementCommand::Major, &repository)?;
@@ -410,7 +391,7 @@ mod test {
     fn increment_should_strip_metadata() -> Result<()> {
         // Arrange
         let repository = Repository::init(".")?;
-        let version = Tag::from_str("1.1.1-pre+10.1", None)?;
+        let version = Tag::from_str("1.1.1-pre+10.1", None, repository.settings())?;
 
         // Act
         let tag = version.bump(IncrementCommand::Patch, &repository)?;
@@ -426,7 +407,7 @@ mod test {
         // Arrange
         let feature = Commit::commit_fixture(CommitType::Feature, false);
         let breaking_change = Commit::commit_fixture(CommitType::Feature, true);
-        let base_version = Tag::from_str("1.0.0", None)?;
+        let base_version = Tag::from_str("1.0.0", None, &Settings::default())?;
 
         // Act
         let version =
@@ -443,7 +424,7 @@ mod test {
         // Arrange
         let feature = Commit::commit_fixture(CommitType::Feature, false);
         let breaking_change = Commit::commit_fixture(CommitType::Feature, true);
-        let base_version = Tag::from_str("0.1.0", None)?;
+        let base_version = Tag::from_str("0.1.0", None, &Settings::default())?;
 
         // Act
         let version =
@@ -460,7 +441,7 @@ mod test {
         // Arrange
         let patch = Commit::commit_fixture(CommitType::BugFix, false);
         let feature = Commit::commit_fixture(CommitType::Feature, false);
-        let base_version = Tag::from_str("0.1.0", None)?;
+        let base_version = Tag::from_str("0.1.0", None, &Settings::default())?;
 
         // Act
         let version = base_version.version_increment_from_commit_history(&[patch, feature]);
@@ -471,12 +452,36 @@ mod test {
         Ok(())
     }
 
+    #[sealed_test]
+    fn should_ignore_reverted_commits() -> Result<()> {
+        // Arrange
+        let repository = Repository::init(".")?;
+        run_cmd!(
+            git commit --allow-empty -q -m "chore: init";
+            git tag 0.1.0;
+            echo feature > feature;
+            git add feature;
+            git commit -q -m "feat: a feature";
+            git commit --allow-empty -q -m "fix: a bug fix";
+            git revert --no-edit HEAD~1;
+        )?;
+        let base_version = Tag::from_str("0.1.0", None, repository.settings())?;
+
+        // Act
+        let tag = base_version.bump(IncrementCommand::Auto, &repository)?;
+
+        // Assert
+        assert_that!(tag.version).is_equal_to(Version::new(0, 1, 1));
+
+        Ok(())
+    }
+
     #[test]
     fn should_fail_without_feature_bug_fix_or_breaking_change_commit() -> Result<()> {
         // Arrange
         let chore = Commit::commit_fixture(CommitType::Chore, false);
         let docs = Commit::commit_fixture(CommitType::Documentation, false);
-        let base_version = Tag::from_str("0.1.0", None)?;
+        let base_version = Tag::from_str("0.1.0", None, &Settings::default())?;
 
         // Act
         let version = base_version.version_increment_from_commit_history(&[chore, docs]);
@@ -512,7 +517,7 @@ suggestion: Please see https://conventionalcommits.org/en/v1.0.0/#summary for mo
             git commit -m "feat: feature package one";
         )?;
 
-        let base_version = Tag::from_str("0.1.0", None)?;
+        let base_version = Tag::from_str("0.1.0", None, repository.settings())?;
 
         // Act
         let tag = base_version.get_monorepo_global_version_from_commit_history(&repository);
@@ -534,7 +539,7 @@ suggestion: Please see https://conventionalcommits.org/en/v1.0.0/#summary for mo
             git add .;
             git commit -m "feat: feature package one";
         )?;
-        let base_version = Tag::from_str("0.1.0", None)?;
+        let base_version = Tag::from_str("0.1.0", None, repository.settings())?;
 
         // Act
         let tag = base_version.auto_global_bump(&repository, Some(Increment::Minor))?;
@@ -660,8 +665,8 @@ suggestion: Please see https://conventionalcommits.org/en/v1.0.0/#summary for mo
         )?;
 
         // Act
-        let tag =
-            Tag::from_str("0.1.0", None)?.auto_global_bump(&repository, Some(Increment::Minor))?;
+        let tag = Tag::from_str("0.1.0", None, repository.settings())?
+            .auto_global_bump(&repository, Some(Increment::Minor))?;
 
         // Assert
         assert_that!(tag.version).is_equal_to(Version::new(0, 2, 0));
@@ -670,7 +675,6 @@ suggestion: Please see https://conventionalcommits.org/en/v1.0.0/#summary for mo
     }
 
     fn init_monorepo() -> Result<Repository> {
-        let repository = Repository::init(".")?;
         let mut packages = HashMap::new();
         packages.insert(
             "one".to_string(),
@@ -685,10 +689,10 @@ suggestion: Please see https://conventionalcommits.org/en/v1.0.0/#summary for mo
             ..Default::default()
         };
 
-        let settings = toml::to_string(&settings)?;
+        let repository = Repository::init(".")?.with_settings(settings);
 
         run_cmd!(
-            echo $settings > cog.toml;
+            echo init > README;
             git add .;
             git commit -m "chore: first commit";
         )?;
@@ -8,7 +8,9 @@ pub enum ChangelogError {
     TemplateNotFound(PathBuf),
     TeraError(tera::Error),
     WriteError(io::Error),
-    SeparatorNotFound(PathBuf),
+    SeparatorNotFound { path: PathBuf, separator: String },
+    ReleaseNotFound { path: PathBuf, version: String },
+    IncompleteRemoteContext(Vec<&'static str>),
 }
 
 impl Display for ChangelogError {
@@ -23,11 +25,24 @@ impl Display for ChangelogError {
             ChangelogError::WriteError(err) => {
                 writeln!(f, "failed to write changelog: \n\t{}", err)
             }
-            ChangelogError::SeparatorNotFound(path) => writeln!(
+            ChangelogError::SeparatorNotFound { path, separator } => writeln!(
                 f,
-                "cannot find default separator '- - -' in {}",
+                "cannot find separator '{}' or an '## [Unreleased]' section in {}",
+                separator,
                 path.as_path().display()
             ),
+            ChangelogError::ReleaseNotFound { path, version } => writeln!(
+                f,
+                "cannot find a release section for {} in {}",
+                version,
+                path.as_path().display()
+            ),
+            ChangelogError::IncompleteRemoteContext(missing) => writeln!(
+                f,
+                "incomplete changelog remote context, missing {} in changelog configuration \
+                and unable to infer it from the 'origin' remote url",
+                missing.join(", ")
+            ),
         }
     }
 }
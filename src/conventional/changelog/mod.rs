@@ -2,26 +2,35 @@ use crate::conventional::changelog::release::Release;
 use crate::conventional::changelog::renderer::Renderer;
 
 use crate::conventional::changelog::error::ChangelogError;
-use crate::conventional::changelog::template::{MonoRepoContext, PackageContext, Template};
+use crate::conventional::changelog::template::{
+    MonoRepoContext, PackageContext, Template, ToContext,
+};
+use crate::settings::Settings;
 
+use itertools::Itertools;
 use std::fs;
 use std::path::Path;
+use tera::Tera;
 
 pub mod error;
+pub(crate) mod reference;
 pub(crate) mod release;
 pub(crate) mod renderer;
 pub(crate) mod serde;
 pub mod template;
 
-const CHANGELOG_SEPARATOR: &str = "- - -";
+pub(crate) const CHANGELOG_SEPARATOR: &str = "- - -";
 
 const DEFAULT_HEADER: &str =
     "# Changelog\nAll notable changes to this project will be documented in this file. \
-See [conventional commits](https://www.conventionalcommits.org/) for commit guidelines.\n\n- - -\n";
+See [conventional commits](https://www.conventionalcommits.org/) for commit guidelines.";
 
 const DEFAULT_FOOTER: &str =
     "Changelog generated by [cocogitto](https://github.com/cocogitto/cocogitto).";
 
+/// Heading of the upcoming changes section in changelogs following <https://keepachangelog.com>
+const UNRELEASED_HEADING: &str = "## [Unreleased]";
+
 pub enum ReleaseType<'a> {
     Standard,
     MonoRepo(MonoRepoContext<'a>),
@@ -29,18 +38,27 @@ pub enum ReleaseType<'a> {
 }
 
 impl Release<'_> {
-    pub fn into_markdown(self, template: Template) -> Result<String, tera::Error> {
-        let mut renderer = Renderer::try_new(template)?;
+    pub fn into_markdown(
+        self,
+        template: Template,
+        settings: &Settings,
+    ) -> Result<String, tera::Error> {
+        let mut renderer = Renderer::try_new(template)?
+            .with_issue_trackers(&settings.changelog.issue_trackers)
+            .with_separator(settings.changelog.separator());
         renderer.render(self)
     }
 
-    pub fn write_to_file<S: AsRef<Path>>(
+    /// Render the release, along with its previous releases, for the given kind of changelog.
+    pub(crate) fn render(
         self,
-        path: S,
         template: Template,
         kind: ReleaseType,
-    ) -> Result<(), ChangelogError> {
-        let renderer = Renderer::try_new(template)?;
+        settings: &Settings,
+    ) -> Result<String, tera::Error> {
+        let renderer = Renderer::try_new(template)?
+            .with_issue_trackers(&settings.changelog.issue_trackers)
+            .with_separator(settings.changelog.separator());
 
         let mut renderer = match kind {
             ReleaseType::Standard => renderer,
@@ -48,27 +66,428 @@ impl Release<'_> {
             ReleaseType::Package(context) => renderer.with_package_context(context),
         };
 
-        let changelog = renderer.render(self)?;
-
-        let mut changelog_content = fs::read_to_string(path.as_ref())
-            .unwrap_or_else(|_| [DEFAULT_HEADER, DEFAULT_FOOTER].join(""));
+        renderer.render(self)
+    }
 
-        let separator_idx = changelog_content.find(CHANGELOG_SEPARATOR);
+    /// Render the release and insert it in the changelog at `path`, returning the rendered release.
+    pub fn write_to_file<S: AsRef<Path>>(
+        self,
+        path: S,
+        template: Template,
+        kind: ReleaseType,
+        settings: &Settings,
+    ) -> Result<String, ChangelogError> {
+        let package_name = match &kind {
+            ReleaseType::Package(context) => Some(context.package_name),
+            _ => None,
+        };
+        let changelog = self.render(template, kind, settings)?;
+        let path = path.as_ref();
 
-        if let Some(idx) = separator_idx {
-            changelog_content.insert(idx + CHANGELOG_SEPARATOR.len(), '\n');
-            changelog_content.insert_str(idx + CHANGELOG_SEPARATOR.len() + 1, &changelog);
-            changelog_content.insert_str(
-                idx + CHANGELOG_SEPARATOR.len() + 1 + changelog.len(),
-                "\n- - -\n",
-            );
-            fs::write(path.as_ref(), changelog_content)?;
+        let mut changelog_content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(_) => new_changelog(settings, package_name)?,
+        };
 
-            Ok(())
+        let separator = settings.changelog.separator();
+        if let Some(idx) = changelog_content.find(separator) {
+            let idx = idx + separator.len();
+            changelog_content.insert_str(idx, &format!("\n{changelog}\n{separator}\n"));
+        } else if let Some(idx) = find_unreleased_end(&changelog_content) {
+            let (before, after) = changelog_content.split_at(idx);
+            let padding = if before.ends_with("\n\n") { "" } else { "\n" };
+            let trailing = if after.is_empty() { "\n" } else { "\n\n" };
+            let release = format!("{padding}{}{trailing}", changelog.trim());
+            changelog_content.insert_str(idx, &release);
         } else {
-            Err(ChangelogError::SeparatorNotFound(
-                path.as_ref().to_path_buf(),
-            ))
+            return Err(ChangelogError::SeparatorNotFound {
+                path: path.to_path_buf(),
+                separator: separator.to_string(),
+            });
         }
+
+        fs::write(path, changelog_content)?;
+        Ok(changelog)
+    }
+}
+
+/// The content of a new changelog, made of the configured header and footer around a separator.
+fn new_changelog(
+    settings: &Settings,
+    package_name: Option<&str>,
+) -> Result<String, ChangelogError> {
+    let changelog = &settings.changelog;
+    let mut context = match settings.get_template_context()? {
+        Some(remote_context) => remote_context.to_context(),
+        None => tera::Context::new(),
+    };
+
+    if let Some(package_name) = package_name {
+        context.insert("package_name", package_name);
+    }
+
+    let header = changelog.header.as_deref().unwrap_or(DEFAULT_HEADER);
+    let footer = changelog.footer.as_deref().unwrap_or(DEFAULT_FOOTER);
+    let header = Tera::one_off(header, &context, false)?;
+    let footer = Tera::one_off(footer, &context, false)?;
+
+    Ok(format!(
+        "{}\n\n{}\n{}",
+        header.trim_end(),
+        changelog.separator(),
+        footer
+    ))
+}
+
+/// Byte offset where a new release goes in a changelog with an `## [Unreleased]` section,
+/// that is the next heading of the same level, the first link reference or the end of the file.
+fn find_unreleased_end(content: &str) -> Option<usize> {
+    let mut lines = line_offsets(content)
+        .skip_while(|(_, line)| !line.trim_end().eq_ignore_ascii_case(UNRELEASED_HEADING));
+    lines.next()?;
+
+    let end = lines
+        .find(|(_, line)| line.starts_with("## ") || is_link_reference(line))
+        .map(|(idx, _)| idx)
+        .unwrap_or(content.len());
+
+    Some(end)
+}
+
+/// Markdown link reference definitions, ex: `[1.0.0]: https://github.com/o/r/compare/0.1.0...1.0.0`
+fn is_link_reference(line: &str) -> bool {
+    line.starts_with('[') && line.contains("]: ")
+}
+
+fn line_offsets(content: &str) -> impl Iterator<Item = (usize, &str)> {
+    content.split_inclusive('\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some((start, line))
+    })
+}
+
+/// Replace every release of the changelog at `path` with `releases`, newest first.
+/// The header, up to the first separator, and the footer, after the last one, are kept.
+/// Without separators, releases are written after the `## [Unreleased]` section,
+/// keeping the link references at the end of the file.
+pub(crate) fn write_releases<S: AsRef<Path>>(
+    path: S,
+    releases: &[String],
+    settings: &Settings,
+    package_name: Option<&str>,
+) -> Result<(), ChangelogError> {
+    let path = path.as_ref();
+    let separator = settings.changelog.separator();
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => new_changelog(settings, package_name)?,
+    };
+
+    if let (Some(first), Some(last)) = (content.find(separator), content.rfind(separator)) {
+        let header = &content[..first + separator.len()];
+        let footer = &content[last + separator.len()..];
+        let releases = releases.join(&format!("\n{separator}\n\n"));
+        fs::write(path, format!("{header}\n{releases}\n{separator}{footer}"))?;
+    } else if let Some(header_end) = find_unreleased_end(&content) {
+        let footer_start = line_offsets(&content)
+            .find(|(idx, line)| *idx >= header_end && is_link_reference(line))
+            .map(|(idx, _)| idx)
+            .unwrap_or(content.len());
+
+        let header = &content[..header_end];
+        let footer = &content[footer_start..];
+        let padding = if header.ends_with("\n\n") { "" } else { "\n" };
+        let trailing = if footer.is_empty() { "\n" } else { "\n\n" };
+        let releases = releases.iter().map(|release| release.trim()).join("\n\n");
+        fs::write(
+            path,
+            format!("{header}{padding}{releases}{trailing}{footer}"),
+        )?;
+    } else {
+        return Err(ChangelogError::SeparatorNotFound {
+            path: path.to_path_buf(),
+            separator: separator.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Replace the release section whose heading mentions `version` in the changelog at `path`.
+/// Sections are delimited by separators, or by release headings in changelogs with an
+/// `## [Unreleased]` section and no separator. The surrounding blank lines are kept as is.
+pub(crate) fn replace_release<S: AsRef<Path>>(
+    path: S,
+    version: &str,
+    release: &str,
+    settings: &Settings,
+) -> Result<(), ChangelogError> {
+    let path = path.as_ref();
+    let separator = settings.changelog.separator();
+    let content = fs::read_to_string(path)?;
+
+    if !content.contains(separator) && find_unreleased_end(&content).is_some() {
+        return replace_heading_section(path, &content, version, release);
+    }
+
+    let mut sections: Vec<&str> = content.split(separator).collect();
+
+    // The first section is the header, the last one the footer
+    let release_sections = 1..sections.len().saturating_sub(1);
+    let idx = release_sections
+        .into_iter()
+        .find(|idx| is_release_of(sections[*idx], version))
+        .ok_or_else(|| ChangelogError::ReleaseNotFound {
+            path: path.to_path_buf(),
+            version: version.to_string(),
+        })?;
+
+    let section = sections[idx];
+    let leading = &section[..section.len() - section.trim_start().len()];
+    let trailing = &section[section.trim_end().len()..];
+    let replaced = format!("{leading}{}{trailing}", release.trim());
+    sections[idx] = &replaced;
+
+    fs::write(path, sections.join(separator))?;
+    Ok(())
+}
+
+/// Replace the release of a changelog without separators, following <https://keepachangelog.com>.
+/// The section spans from its heading to the next release heading or the link references.
+fn replace_heading_section(
+    path: &Path,
+    content: &str,
+    version: &str,
+    release: &str,
+) -> Result<(), ChangelogError> {
+    let mut lines = line_offsets(content)
+        .skip_while(|(_, line)| !(line.starts_with("## ") && is_release_of(line, version)));
+
+    let start =
+        lines
+            .next()
+            .map(|(idx, _)| idx)
+            .ok_or_else(|| ChangelogError::ReleaseNotFound {
+                path: path.to_path_buf(),
+                version: version.to_string(),
+            })?;
+
+    let end = lines
+        .find(|(_, line)| line.starts_with("## ") || is_link_reference(line))
+        .map(|(idx, _)| idx)
+        .unwrap_or(content.len());
+
+    let section = &content[start..end];
+    let trailing = &section[section.trim_end().len()..];
+    let replaced = format!(
+        "{}{}{trailing}{}",
+        &content[..start],
+        release.trim(),
+        &content[end..]
+    );
+
+    fs::write(path, replaced)?;
+    Ok(())
+}
+
+/// Whether the heading of a changelog section mentions `version`, ex: `## 1.0.0 - 2022-01-01`
+/// or `## [1.0.0](https://github.com/cocogitto/cocogitto/compare/0.1.0..1.0.0) - 2022-01-01`.
+fn is_release_of(section: &str, version: &str) -> bool {
+    let heading = match section.lines().map(str::trim).find(|line| !line.is_empty()) {
+        Some(heading) if heading.starts_with('#') => heading,
+        _ => return false,
+    };
+
+    let is_version_char = |c: char| c.is_alphanumeric() || matches!(c, '.' | '-' | '+' | '_');
+    heading.match_indices(version).any(|(idx, _)| {
+        let before = heading[..idx].chars().next_back();
+        let after = heading[idx + version.len()..].chars().next();
+        !before.is_some_and(is_version_char) && !after.is_some_and(is_version_char)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::conventional::changelog::{is_release_of, replace_release, write_releases};
+    use crate::settings::Settings;
+    use anyhow::Result;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use sealed_test::prelude::*;
+    use speculoos::prelude::*;
+    use std::fs;
+
+    #[test]
+    fn should_match_release_heading() {
+        assert_that!(is_release_of("\n## 1.0.0 - 2022-01-01\n", "1.0.0")).is_true();
+        assert_that!(is_release_of(
+            "\n## [1.0.1](https://github.com/o/r/compare/1.0.0..1.0.1) - 2022-01-01\n",
+            "1.0.1"
+        ))
+        .is_true();
+        assert_that!(is_release_of(
+            "\n## [1.0.1](https://github.com/o/r/compare/1.0.0..1.0.1) - 2022-01-01\n",
+            "1.0.0"
+        ))
+        .is_false();
+        assert_that!(is_release_of("\n## one-1.0.0 - 2022-01-01\n", "1.0.0")).is_false();
+        assert_that!(is_release_of("\n- fix 1.0.0 regression\n", "1.0.0")).is_false();
+    }
+
+    #[sealed_test]
+    fn should_replace_release_section() -> Result<()> {
+        // Arrange
+        fs::write(
+            "CHANGELOG.md",
+            indoc! {"
+                # Changelog
+                - - -
+                ## 1.0.1 - 2022-01-02
+                - a fix
+
+                - - -
+
+                ## 1.0.0 - 2022-01-01
+                - a tpyo
+
+                - - -
+
+                Footer"},
+        )?;
+
+        // Act
+        replace_release(
+            "CHANGELOG.md",
+            "1.0.0",
+            "## 1.0.0 - 2022-01-01\n- a typo\n",
+            &Settings::default(),
+        )?;
+
+        // Assert
+        assert_eq!(
+            fs::read_to_string("CHANGELOG.md")?,
+            indoc! {"
+                # Changelog
+                - - -
+                ## 1.0.1 - 2022-01-02
+                - a fix
+
+                - - -
+
+                ## 1.0.0 - 2022-01-01
+                - a typo
+
+                - - -
+
+                Footer"}
+        );
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn should_write_releases_after_unreleased_section() -> Result<()> {
+        // Arrange
+        fs::write(
+            "CHANGELOG.md",
+            indoc! {"
+                # Changelog
+
+                ## [Unreleased]
+                - upcoming change
+
+                ## 0.1.0 - outdated
+                - stale entry
+
+                [Unreleased]: https://github.com/o/r/compare/1.0.0...HEAD
+            "},
+        )?;
+        let releases = vec![
+            "## 1.0.0 - 2022-01-02\n- a feature\n".to_string(),
+            "## 0.1.0 - 2022-01-01\n- a fix\n".to_string(),
+        ];
+
+        // Act
+        write_releases("CHANGELOG.md", &releases, &Settings::default(), None)?;
+
+        // Assert
+        assert_eq!(
+            fs::read_to_string("CHANGELOG.md")?,
+            indoc! {"
+                # Changelog
+
+                ## [Unreleased]
+                - upcoming change
+
+                ## 1.0.0 - 2022-01-02
+                - a feature
+
+                ## 0.1.0 - 2022-01-01
+                - a fix
+
+                [Unreleased]: https://github.com/o/r/compare/1.0.0...HEAD
+            "}
+        );
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn should_replace_release_after_unreleased_section() -> Result<()> {
+        // Arrange
+        fs::write(
+            "CHANGELOG.md",
+            indoc! {"
+                # Changelog
+
+                ## [Unreleased]
+
+                ## 1.0.0 - 2022-01-02
+                - a feature
+
+                ## 0.1.0 - 2022-01-01
+                - a tpyo
+            "},
+        )?;
+
+        // Act
+        replace_release(
+            "CHANGELOG.md",
+            "0.1.0",
+            "## 0.1.0 - 2022-01-01\n- a typo\n",
+            &Settings::default(),
+        )?;
+
+        // Assert
+        assert_eq!(
+            fs::read_to_string("CHANGELOG.md")?,
+            indoc! {"
+                # Changelog
+
+                ## [Unreleased]
+
+                ## 1.0.0 - 2022-01-02
+                - a feature
+
+                ## 0.1.0 - 2022-01-01
+                - a typo
+            "}
+        );
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn should_fail_to_replace_unknown_release() -> Result<()> {
+        // Arrange
+        fs::write(
+            "CHANGELOG.md",
+            "# Changelog\n- - -\n## 1.0.0\n- - -\nFooter",
+        )?;
+
+        // Act
+        let result = replace_release("CHANGELOG.md", "2.0.0", "## 2.0.0\n", &Settings::default());
+
+        // Assert
+        assert_that!(result).is_err();
+        Ok(())
     }
 }
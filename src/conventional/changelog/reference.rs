@@ -0,0 +1,278 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use conventional_commit_parser::commit::{ConventionalCommit, Separator};
+use tera::{try_get_value, Filter, Value};
+
+use crate::conventional::changelog::template::RemoteContext;
+use crate::settings::IssueTracker;
+
+const ISSUE_ID_PLACEHOLDER: &str = "{{id}}";
+
+/// Link issue references (ex: `#123`, `JIRA-456`) to their issue tracker,
+/// exposed to changelog templates as the `link_references` filter.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IssueLinker {
+    trackers: Vec<IssueTracker>,
+}
+
+impl IssueLinker {
+    /// Issues trackers from the configuration, `#` references default to the remote
+    /// issues if the remote context is set and no tracker was configured for them.
+    pub(crate) fn new(trackers: &[IssueTracker], remote: Option<&RemoteContext>) -> Self {
+        let mut trackers = trackers.to_vec();
+
+        if let Some(remote) = remote {
+            if !trackers.iter().any(|tracker| tracker.prefix == "#") {
+                trackers.push(IssueTracker {
+                    prefix: "#".to_string(),
+                    url: remote.issue_url(ISSUE_ID_PLACEHOLDER),
+                });
+            }
+        }
+
+        Self { trackers }
+    }
+
+    /// Replace every known reference in `text` with a markdown link.
+    pub(crate) fn link(&self, text: &str) -> String {
+        let prefixes = self.trackers.iter().map(|tracker| tracker.prefix.as_str());
+        let mut linked = String::with_capacity(text.len());
+        let mut last = 0;
+
+        for (range, prefix_idx) in find_references(text, prefixes) {
+            let reference = &text[range.clone()];
+            let tracker = &self.trackers[prefix_idx];
+            let id = &reference[tracker.prefix.len()..];
+            let url = tracker.url.replace(ISSUE_ID_PLACEHOLDER, id);
+            linked.push_str(&text[last..range.start]);
+            linked.push_str(&format!("[{reference}]({url})"));
+            last = range.end;
+        }
+
+        linked.push_str(&text[last..]);
+        linked
+    }
+}
+
+impl Filter for IssueLinker {
+    fn filter(&self, value: &Value, _: &HashMap<String, Value>) -> tera::Result<Value> {
+        let text = try_get_value!("link_references", "value", String, value);
+        Ok(Value::String(self.link(&text)))
+    }
+}
+
+/// Issue references found in a commit footers (ex: `Closes #123` or `Refs: JIRA-456`)
+/// that are not already mentioned in the commit summary.
+pub(crate) fn footer_references(
+    message: &ConventionalCommit,
+    trackers: &[IssueTracker],
+) -> Vec<String> {
+    let prefixes: Vec<&str> = trackers
+        .iter()
+        .map(|tracker| tracker.prefix.as_str())
+        .chain(Some("#"))
+        .collect();
+
+    let mut references = vec![];
+    for footer in &message.footers {
+        let content = match footer.token_separator {
+            Separator::Hash => format!("#{}", footer.content),
+            _ => footer.content.clone(),
+        };
+
+        for (range, _) in find_references(&content, prefixes.iter().copied()) {
+            references.push(content[range].to_string());
+        }
+    }
+
+    let mentioned: Vec<String> = find_references(&message.summary, prefixes.iter().copied())
+        .into_iter()
+        .map(|(range, _)| message.summary[range].to_string())
+        .collect();
+
+    let mut seen = HashSet::new();
+    references.retain(|reference| !mentioned.contains(reference) && seen.insert(reference.clone()));
+    references
+}
+
+/// Returns the byte range of each reference found in `text`, along with the index of the matching prefix.
+/// A reference is a prefix directly followed by digits, not preceded by an alphanumeric character,
+/// and not already part of a markdown link or url.
+fn find_references<'a>(
+    text: &str,
+    prefixes: impl Iterator<Item = &'a str>,
+) -> Vec<(Range<usize>, usize)> {
+    let prefixes: Vec<&str> = prefixes.collect();
+    let mut references = vec![];
+    let mut idx = 0;
+
+    while idx < text.len() {
+        let preceding = text[..idx].chars().next_back();
+        let is_boundary = preceding
+            .map(|char| !(char.is_alphanumeric() || matches!(char, '[' | '/' | '&' | '-' | '_')))
+            .unwrap_or(true);
+
+        let matched = prefixes
+            .iter()
+            .enumerate()
+            .filter(|(_, prefix)| {
+                is_boundary && !prefix.is_empty() && text[idx..].starts_with(*prefix)
+            })
+            .find_map(|(prefix_idx, prefix)| {
+                let start = idx + prefix.len();
+                let digits = text[start..]
+                    .find(|char: char| !char.is_ascii_digit())
+                    .unwrap_or(text.len() - start);
+                let end = start + digits;
+                let followed_by_word = text[end..]
+                    .chars()
+                    .next()
+                    .map(char::is_alphanumeric)
+                    .unwrap_or(false);
+
+                (digits > 0 && !followed_by_word).then_some((idx..end, prefix_idx))
+            });
+
+        match matched {
+            Some((range, prefix_idx)) => {
+                idx = range.end;
+                references.push((range, prefix_idx));
+            }
+            None => {
+                idx += text[idx..].chars().next().map(char::len_utf8).unwrap_or(1);
+            }
+        }
+    }
+
+    references
+}
+
+#[cfg(test)]
+mod test {
+    use conventional_commit_parser::commit::{CommitType, ConventionalCommit, Footer, Separator};
+    use speculoos::prelude::*;
+
+    use crate::conventional::changelog::reference::{footer_references, IssueLinker};
+    use crate::conventional::changelog::template::RemoteContext;
+    use crate::settings::IssueTracker;
+
+    fn jira() -> IssueTracker {
+        IssueTracker {
+            prefix: "JIRA-".to_string(),
+            url: "https://jira.example.com/browse/JIRA-{{id}}".to_string(),
+        }
+    }
+
+    #[test]
+    fn should_link_remote_and_configured_references() {
+        // Arrange
+        let remote = RemoteContext::try_new(
+            Some("github.com".into()),
+            Some("cocogitto".into()),
+            Some("cocogitto".into()),
+        )
+        .unwrap();
+        let linker = IssueLinker::new(&[jira()], remote.as_ref());
+
+        // Act
+        let linked = linker.link("fix parser (#12), see JIRA-456 and foo#3");
+
+        // Assert
+        assert_that!(linked).is_equal_to(
+            "fix parser ([#12](https://github.com/cocogitto/cocogitto/issues/12)), \
+            see [JIRA-456](https://jira.example.com/browse/JIRA-456) and foo#3"
+                .to_string(),
+        );
+    }
+
+    #[test]
+    fn should_not_link_without_tracker() {
+        // Arrange
+        let linker = IssueLinker::new(&[], None);
+
+        // Act
+        let linked = linker.link("fix parser (#12)");
+
+        // Assert
+        assert_that!(linked).is_equal_to("fix parser (#12)".to_string());
+    }
+
+    #[test]
+    fn should_not_link_already_linked_references() {
+        // Arrange
+        let linker = IssueLinker::new(&[jira()], None);
+
+        // Act
+        let linked = linker.link("[JIRA-1](https://jira.example.com/browse/JIRA-1) JIRA-2a");
+
+        // Assert
+        assert_that!(linked)
+            .is_equal_to("[JIRA-1](https://jira.example.com/browse/JIRA-1) JIRA-2a".to_string());
+    }
+
+    #[test]
+    fn should_get_footer_references() {
+        // Arrange
+        let message = ConventionalCommit {
+            commit_type: CommitType::BugFix,
+            scope: None,
+            summary: "fix parser (#12)".to_string(),
+            body: None,
+            footers: vec![
+                Footer {
+                    token: "Closes".to_string(),
+                    content: "123".to_string(),
+                    token_separator: Separator::Hash,
+                },
+                Footer {
+                    token: "Refs".to_string(),
+                    content: "JIRA-456, #12".to_string(),
+                    ..Default::default()
+                },
+                Footer {
+                    token: "Reviewed-by".to_string(),
+                    content: "Paul".to_string(),
+                    ..Default::default()
+                },
+            ],
+            is_breaking_change: false,
+        };
+
+        // Act
+        let references = footer_references(&message, &[jira()]);
+
+        // Assert
+        assert_that!(references).is_equal_to(vec!["#123".to_string(), "JIRA-456".to_string()]);
+    }
+
+    #[test]
+    fn should_deduplicate_footer_references() {
+        // Arrange
+        let message = ConventionalCommit {
+            commit_type: CommitType::BugFix,
+            scope: None,
+            summary: "fix parser".to_string(),
+            body: None,
+            footers: vec![
+                Footer {
+                    token: "Refs".to_string(),
+                    content: "#12, #13".to_string(),
+                    ..Default::default()
+                },
+                Footer {
+                    token: "Closes".to_string(),
+                    content: "12".to_string(),
+                    token_separator: Separator::Hash,
+                },
+            ],
+            is_breaking_change: false,
+        };
+
+        // Act
+        let references = footer_references(&message, &[]);
+
+        // Assert
+        assert_that!(references).is_equal_to(vec!["#12".to_string(), "#13".to_string()]);
+    }
+}
@@ -2,10 +2,10 @@ use chrono::{NaiveDateTime, Utc};
 use conventional_commit_parser::commit::Footer;
 use serde::Serialize;
 
-use crate::conventional::commit::Commit;
+use crate::conventional::commit::{drop_reverted_commits, Commit};
 use crate::git::oid::OidOf;
 use crate::git::revspec::CommitRange;
-use crate::{settings, SETTINGS};
+use crate::settings::{IssueTracker, Settings};
 use colored::Colorize;
 use git2::Oid;
 use log::warn;
@@ -49,18 +49,22 @@ impl Release<'_> {
     }
 }
 
-impl<'a> From<CommitRange<'a>> for Release<'a> {
-    fn from(commit_range: CommitRange<'a>) -> Self {
+impl<'a> Release<'a> {
+    pub(crate) fn from_commit_range(commit_range: CommitRange<'_>, settings: &'a Settings) -> Self {
         let mut commits = vec![];
 
-        for commit in commit_range.commits {
+        for commit in drop_reverted_commits(commit_range.commits) {
             // Ignore merge commits
-            if commit.parent_count() > 1 && SETTINGS.ignore_merge_commits {
+            if commit.parent_count() > 1 && settings.ignore_merge_commits {
                 continue;
             }
 
-            match Commit::from_git_commit(&commit) {
-                Ok(commit) => commits.push(ChangelogCommit::from(commit)),
+            match Commit::from_squashed_git_commit(&commit, settings) {
+                Ok(squashed) => commits.extend(
+                    squashed
+                        .into_iter()
+                        .map(|commit| ChangelogCommit::new(commit, settings)),
+                ),
                 Err(err) => {
                     let err = err.to_string().red();
                     warn!("{}", err);
@@ -71,7 +75,7 @@ impl<'a> From<CommitRange<'a>> for Release<'a> {
         Release {
             version: commit_range.to,
             from: commit_range.from,
-            date: Utc::now().naive_utc(),
+            date: commit_range.date.unwrap_or_else(|| Utc::now().naive_utc()),
             commits,
             previous: None,
         }
@@ -81,15 +85,26 @@ impl<'a> From<CommitRange<'a>> for Release<'a> {
 #[derive(Debug)]
 pub struct ChangelogCommit<'a> {
     pub author_username: Option<&'a str>,
+    /// The changelog section title of the commit type
+    pub changelog_title: String,
+    /// Issue trackers used to find references in the commit footers
+    pub issue_trackers: &'a [IssueTracker],
     pub commit: Commit,
 }
 
-impl From<Commit> for ChangelogCommit<'_> {
-    fn from(commit: Commit) -> Self {
-        let author_username = settings::commit_username(&commit.author);
+impl<'a> ChangelogCommit<'a> {
+    pub(crate) fn new(commit: Commit, settings: &'a Settings) -> Self {
+        let author_username = settings.commit_username(&commit.author);
+        let changelog_title = settings
+            .commit_types()
+            .get(&commit.message.commit_type)
+            .map(|config| config.changelog_title.clone())
+            .unwrap_or_else(|| commit.message.commit_type.to_string());
 
         ChangelogCommit {
             author_username,
+            changelog_title,
+            issue_trackers: &settings.changelog.issue_trackers,
             commit,
         }
     }
@@ -114,10 +129,13 @@ impl<'a> From<&'a Footer> for ChangelogFooter<'a> {
 mod test {
     use anyhow::Result;
     use chrono::NaiveDateTime;
-    use conventional_commit_parser::commit::{CommitType, ConventionalCommit, Footer};
+    use cmd_lib::{run_cmd, run_fun};
+    use conventional_commit_parser::commit::{CommitType, ConventionalCommit, Footer, Separator};
     use git2::Oid;
     use indoc::indoc;
     use pretty_assertions::assert_eq;
+    use sealed_test::prelude::*;
+    use speculoos::prelude::*;
 
     use crate::conventional::changelog::release::{ChangelogCommit, Release};
     use crate::conventional::changelog::renderer::Renderer;
@@ -127,6 +145,8 @@ mod test {
     use crate::conventional::commit::Commit;
     use crate::git::oid::OidOf;
     use crate::git::tag::Tag;
+    use crate::settings::Settings;
+    use crate::{Repository, RevspecPattern};
 
     #[test]
     fn should_render_default_template() -> Result<()> {
@@ -192,7 +212,7 @@ mod test {
                 Some("github.com".into()),
                 Some("cocogitto".into()),
                 Some("cocogitto".into()),
-            ),
+            )?,
             kind: TemplateKind::Remote,
         })?;
 
@@ -216,6 +236,80 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn should_render_gitlab_template() -> Result<()> {
+        // Arrange
+        let release = Release::fixture();
+        let mut renderer = Renderer::try_new(Template {
+            remote_context: RemoteContext::try_new(
+                Some("gitlab.com".into()),
+                Some("cocogitto".into()),
+                Some("cocogitto/tools".into()),
+            )?,
+            kind: TemplateKind::Remote,
+        })?;
+
+        // Act
+        let changelog = renderer.render(release)?;
+
+        // Assert
+        assert_eq!(
+            changelog,
+            indoc! {
+                "## [1.0.0](https://gitlab.com/cocogitto/tools/cocogitto/-/compare/0.1.0...1.0.0) - 2015-09-05
+                #### Bug Fixes
+                - **(parser)** fix parser implementation - ([17f7e23](https://gitlab.com/cocogitto/tools/cocogitto/-/commit/17f7e23081db15e9318aeb37529b1d473cf41cbe)) - [@oknozor](https://gitlab.com/oknozor)
+                #### Features
+                - **(parser)** implement the changelog generator - ([17f7e23](https://gitlab.com/cocogitto/tools/cocogitto/-/commit/17f7e23081db15e9318aeb37529b1d473cf41cbe)) - [@oknozor](https://gitlab.com/oknozor)
+                - awesome feature - ([17f7e23](https://gitlab.com/cocogitto/tools/cocogitto/-/commit/17f7e23081db15e9318aeb37529b1d473cf41cbe)) - Paul Delafosse
+                "
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_github_template_with_issue_references() -> Result<()> {
+        // Arrange
+        let mut release = Release::fixture();
+        let fix = &mut release.commits[0].commit.message;
+        fix.summary = "fix parser implementation (#12)".to_string();
+        fix.footers = vec![Footer {
+            token: "Closes".to_string(),
+            content: "123".to_string(),
+            token_separator: Separator::Hash,
+        }];
+
+        let mut renderer = Renderer::try_new(Template {
+            remote_context: RemoteContext::try_new(
+                Some("github.com".into()),
+                Some("cocogitto".into()),
+                Some("cocogitto".into()),
+            )?,
+            kind: TemplateKind::Remote,
+        })?;
+
+        // Act
+        let changelog = renderer.render(release)?;
+
+        // Assert
+        assert_eq!(
+            changelog,
+            indoc! {
+                "## [1.0.0](https://github.com/cocogitto/cocogitto/compare/0.1.0..1.0.0) - 2015-09-05
+                #### Bug Fixes
+                - **(parser)** fix parser implementation ([#12](https://github.com/cocogitto/cocogitto/issues/12)) ([#123](https://github.com/cocogitto/cocogitto/issues/123)) - ([17f7e23](https://github.com/cocogitto/cocogitto/commit/17f7e23081db15e9318aeb37529b1d473cf41cbe)) - [@oknozor](https://github.com/oknozor)
+                #### Features
+                - **(parser)** implement the changelog generator - ([17f7e23](https://github.com/cocogitto/cocogitto/commit/17f7e23081db15e9318aeb37529b1d473cf41cbe)) - [@oknozor](https://github.com/oknozor)
+                - awesome feature - ([17f7e23](https://github.com/cocogitto/cocogitto/commit/17f7e23081db15e9318aeb37529b1d473cf41cbe)) - Paul Delafosse
+                "
+            }
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn should_render_template_monorepo() -> Result<()> {
         // Arrange
@@ -365,7 +459,7 @@ mod test {
                 Some("github.com".into()),
                 Some("cocogitto".into()),
                 Some("cocogitto".into()),
-            ),
+            )?,
             kind: TemplateKind::MonorepoRemote,
         })?;
 
@@ -466,7 +560,7 @@ mod test {
                 Some("github.com".into()),
                 Some("cocogitto".into()),
                 Some("cocogitto".into()),
-            ),
+            )?,
             kind: TemplateKind::PackageRemote,
         })?;
 
@@ -501,7 +595,7 @@ mod test {
                 Some("github.com".into()),
                 Some("cocogitto".into()),
                 Some("cocogitto".into()),
-            ),
+            )?,
             kind: TemplateKind::MonorepoRemote,
         })?;
 
@@ -531,6 +625,66 @@ mod test {
         Ok(())
     }
 
+    #[sealed_test]
+    fn should_drop_reverted_commits_in_range() -> Result<()> {
+        // Arrange
+        run_cmd!(
+            git init;
+            git commit --allow-empty -q -m "chore: init";
+            git tag 0.1.0;
+            echo "feature" > file;
+            git add .;
+            git commit -q -m "feat: a feature";
+            git revert --no-edit HEAD;
+            git commit --allow-empty -q -m "fix: a fix";
+        )?;
+
+        let repo = Repository::open(".")?;
+        let range = repo.get_commit_range(&RevspecPattern::from("0.1.0..HEAD"))?;
+
+        // Act
+        let release = Release::from_commit_range(range, repo.settings());
+
+        // Assert
+        assert_that!(release.commits)
+            .matches(|commits| commits[0].commit.message.summary == "a fix")
+            .has_length(1);
+
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn should_keep_revert_of_previously_released_commit() -> Result<()> {
+        // Arrange
+        let oid = run_fun!(
+            git init;
+            echo "feature" > file;
+            git add .;
+            git commit -q -m "feat: a feature";
+            git tag 0.1.0;
+            git log --format=%H -n 1;
+        )?;
+
+        let revert_body = format!("This reverts commit {oid}.");
+        run_cmd!(
+            git rm -q file;
+            git commit -q -m "revert: a feature" -m $revert_body;
+        )?;
+
+        let repo = Repository::open(".")?;
+        let range = repo.get_commit_range(&RevspecPattern::from("0.1.0..HEAD"))?;
+
+        // Act
+        let release = Release::from_commit_range(range, repo.settings());
+
+        // Assert
+        assert_that!(release.commits)
+            .matches(|commits| commits[0].commit.message.commit_type == CommitType::Revert)
+            .has_length(1);
+
+        Ok(())
+    }
+
     impl Release<'_> {
         pub fn fixture() -> Release<'static> {
             let date =
@@ -541,11 +695,13 @@ mod test {
             let version = Tag::from_str(
                 "1.0.0",
                 Some(Oid::from_str("9bb5facac5724bc81385fdd740fedbb49056da00").unwrap()),
+                &Settings::default(),
             )
             .unwrap();
             let from = Tag::from_str(
                 "0.1.0",
                 Some(Oid::from_str("fae3a288a1bc69b14f85a1d5fe57cee1964acd60").unwrap()),
+                &Settings::default(),
             )
             .unwrap();
             Release {
@@ -554,6 +710,8 @@ mod test {
                 date,
                 commits: vec![
                     ChangelogCommit {
+                        changelog_title: "Bug Fixes".to_string(),
+                        issue_trackers: &[],
                         author_username: Some("oknozor"),
                         commit: Commit {
                             oid: a_commit_hash.to_string(),
@@ -574,6 +732,8 @@ mod test {
                         },
                     },
                     ChangelogCommit {
+                        changelog_title: "Features".to_string(),
+                        issue_trackers: &[],
                         author_username: None,
                         commit: Commit {
                             oid: a_commit_hash.to_string(),
@@ -594,6 +754,8 @@ mod test {
                         },
                     },
                     ChangelogCommit {
+                        changelog_title: "Features".to_string(),
+                        issue_trackers: &[],
                         author_username: Some("oknozor"),
                         commit: Commit {
                             oid: a_commit_hash.to_string(),
@@ -629,10 +791,12 @@ mod test {
                     version: OidOf::Tag(Tag::from_str(
                         "0.1.0",
                         Some(Oid::from_str("fae3a288a1bc69b14f85a1d5fe57cee1964acd60").unwrap()),
+                        &Settings::default(),
                     )?),
                     from: Some(OidOf::Tag(Tag::from_str(
                         "0.2.0",
                         Some(Oid::from_str("fae3a288a1bc69b14f85a1d5fe57cee1964acd60").unwrap()),
+                        &Settings::default(),
                     )?)),
                 },
                 PackageBumpContext {
@@ -641,10 +805,12 @@ mod test {
                     version: OidOf::Tag(Tag::from_str(
                         "0.2.0",
                         Some(Oid::from_str("fae3a288a1bc69b14f85a1d5fe57cee1964acd60").unwrap()),
+                        &Settings::default(),
                     )?),
                     from: Some(OidOf::Tag(Tag::from_str(
                         "0.3.0",
                         Some(Oid::from_str("fae3a288a1bc69b14f85a1d5fe57cee1964acd60").unwrap()),
+                        &Settings::default(),
                     )?)),
                 },
             ],
@@ -663,6 +829,7 @@ mod test {
                     version: OidOf::Tag(Tag::from_str(
                         "0.1.0",
                         Some(Oid::from_str("fae3a288a1bc69b14f85a1d5fe57cee1964acd60").unwrap()),
+                        &Settings::default(),
                     )?),
                     from: None,
                 },
@@ -672,6 +839,7 @@ mod test {
                     version: OidOf::Tag(Tag::from_str(
                         "0.2.0",
                         Some(Oid::from_str("fae3a288a1bc69b14f85a1d5fe57cee1964acd60").unwrap()),
+                        &Settings::default(),
                     )?),
                     from: None,
                 },
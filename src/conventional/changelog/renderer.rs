@@ -2,16 +2,23 @@ use std::collections::HashMap;
 
 use tera::{get_json_pointer, to_value, try_get_value, Context, Tera, Value};
 
+use crate::conventional::changelog::reference::IssueLinker;
 use crate::conventional::changelog::release::Release;
 use crate::conventional::changelog::template::{
     MonoRepoContext, PackageContext, RemoteContext, Template, ToContext,
 };
+use crate::conventional::changelog::CHANGELOG_SEPARATOR;
+use crate::settings::IssueTracker;
+
+type RemoteUrl = fn(&RemoteContext, &[String]) -> String;
 
 #[derive(Debug)]
 pub struct Renderer {
     tera: Tera,
     context: Context,
     template: Template,
+    /// Emitted between a release and its previous ones
+    separator: String,
 }
 
 impl Default for Renderer {
@@ -29,14 +36,36 @@ impl Renderer {
         tera.add_raw_template(template.kind.name(), content.as_ref())?;
         tera.register_filter("upper_first", Self::upper_first_filter);
         tera.register_filter("unscoped", Self::unscoped);
+        tera.register_filter(
+            "link_references",
+            IssueLinker::new(&[], template.remote_context.as_ref()),
+        );
+
+        Self::register_remote_functions(&mut tera, template.remote_context.as_ref());
 
         Ok(Renderer {
             tera,
             context: Context::new(),
             template,
+            separator: CHANGELOG_SEPARATOR.to_string(),
         })
     }
 
+    /// Separate the previous releases with `separator` instead of the default one
+    pub(crate) fn with_separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Link references to the given issue trackers with the `link_references` filter
+    pub(crate) fn with_issue_trackers(mut self, trackers: &[IssueTracker]) -> Self {
+        self.tera.register_filter(
+            "link_references",
+            IssueLinker::new(trackers, self.template.remote_context.as_ref()),
+        );
+        self
+    }
+
     pub(crate) fn with_package_context(mut self, context: PackageContext) -> Self {
         self.context.extend(context.to_context());
         self
@@ -51,7 +80,7 @@ impl Renderer {
         let mut release = self.render_release(&version)?;
         let mut version = version;
         while let Some(previous) = version.previous.map(|v| *v) {
-            release.push_str("\n- - -\n\n");
+            release.push_str(&format!("\n{}\n\n", self.separator));
             release.push_str(self.render_release(&previous)?.as_str());
             version = previous;
         }
@@ -75,6 +104,48 @@ impl Renderer {
         self.tera.render(self.template.kind.name(), &self.context)
     }
 
+    // Platform aware links, ex: `{{ commit_url(id=commit.id) }}`
+    fn register_remote_functions(tera: &mut Tera, remote: Option<&RemoteContext>) {
+        let functions: [(&'static str, &'static [&'static str], RemoteUrl); 5] = [
+            ("compare_url", &["from", "to"], |remote, args| {
+                remote.compare_url(&args[0], &args[1])
+            }),
+            ("commit_url", &["id"], |remote, args| {
+                remote.commit_url(&args[0])
+            }),
+            ("issue_url", &["id"], |remote, args| {
+                remote.issue_url(&args[0])
+            }),
+            ("tag_url", &["tag"], |remote, args| remote.tag_url(&args[0])),
+            ("user_url", &["username"], |remote, args| {
+                remote.user_url(&args[0])
+            }),
+        ];
+
+        for (name, params, url) in functions {
+            let remote = remote.cloned();
+            tera.register_function(name, move |args: &HashMap<String, Value>| {
+                let remote = remote.as_ref().ok_or_else(|| {
+                    tera::Error::msg(format!(
+                        "'{name}' requires the changelog 'remote', 'owner' and 'repository' to be set"
+                    ))
+                })?;
+
+                let args: Vec<String> = params
+                    .iter()
+                    .map(|param| match args.get(*param) {
+                        Some(Value::String(value)) => Ok(value.clone()),
+                        _ => Err(tera::Error::msg(format!(
+                            "'{name}' expects a string '{param}' argument"
+                        ))),
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Value::String(url(remote, &args)))
+            });
+        }
+    }
+
     // From git-cliff: https://github.com/orhun/git-cliff/blob/main/git-cliff-core/src/template.rs
     fn upper_first_filter(value: &Value, _: &HashMap<String, Value>) -> Result<Value, tera::Error> {
         let mut s = tera::try_get_value!("upper_first_filter", "value", String, value);
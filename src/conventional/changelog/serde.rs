@@ -1,10 +1,10 @@
 use serde::ser::SerializeStruct;
 use serde::{Serialize, Serializer};
 
+use crate::conventional::changelog::reference::footer_references;
 use crate::conventional::changelog::release::{ChangelogCommit, ChangelogFooter};
 use crate::git::oid::OidOf;
 use crate::git::tag::Tag;
-use crate::COMMITS_METADATA;
 
 impl Serialize for Tag {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -20,7 +20,7 @@ impl Serialize for ChangelogCommit<'_> {
     where
         S: Serializer,
     {
-        let mut commit = serializer.serialize_struct("Commit", 10)?;
+        let mut commit = serializer.serialize_struct("Commit", 11)?;
 
         let footers = &self
             .commit
@@ -30,21 +30,18 @@ impl Serialize for ChangelogCommit<'_> {
             .map(ChangelogFooter::from)
             .collect::<Vec<ChangelogFooter>>();
 
-        let commit_type = &COMMITS_METADATA
-            .iter()
-            .find(|(commit_type, _config)| *commit_type == &self.commit.message.commit_type)
-            .map(|meta| meta.1.changelog_title.clone())
-            .unwrap_or_else(|| self.commit.message.commit_type.to_string());
+        let references = footer_references(&self.commit.message, self.issue_trackers);
 
         commit.serialize_field("id", &self.commit.oid)?;
         commit.serialize_field("author", &self.author_username)?;
         commit.serialize_field("signature", &self.commit.author)?;
-        commit.serialize_field("type", commit_type)?;
+        commit.serialize_field("type", &self.changelog_title)?;
         commit.serialize_field("date", &self.commit.date)?;
         commit.serialize_field("scope", &self.commit.message.scope)?;
         commit.serialize_field("summary", &self.commit.message.summary)?;
         commit.serialize_field("body", &self.commit.message.body)?;
         commit.serialize_field("breaking_change", &self.commit.message.is_breaking_change)?;
+        commit.serialize_field("references", &references)?;
         commit.serialize_field("footer", footers)?;
         commit.end()
     }
@@ -81,10 +78,16 @@ mod test {
     use crate::conventional::changelog::release::ChangelogCommit;
     use crate::conventional::commit::Commit;
     use crate::git::tag::Tag;
+    use crate::settings::Settings;
 
     #[test]
     fn should_serialize_tag() {
-        let tag = Tag::from_str("1.0.0", Some(Oid::from_str("1234567890").unwrap())).unwrap();
+        let tag = Tag::from_str(
+            "1.0.0",
+            Some(Oid::from_str("1234567890").unwrap()),
+            &Settings::default(),
+        )
+        .unwrap();
 
         let result = toml::to_string(&tag);
 
@@ -97,6 +100,8 @@ mod test {
     fn should_serialize_commit() {
         let commit = ChangelogCommit {
             author_username: Some("Jm Doudou"),
+            changelog_title: "Bug Fixes".to_string(),
+            issue_trackers: &[],
             commit: Commit {
                 oid: "1234567890".to_string(),
                 message: ConventionalCommit {
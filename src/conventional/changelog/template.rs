@@ -1,6 +1,7 @@
 use crate::conventional::changelog::error::ChangelogError;
 
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use crate::git::oid::OidOf;
 use std::io;
@@ -67,7 +68,7 @@ impl Default for TemplateKind {
 
 impl TemplateKind {
     /// Returns either a predefined template or a custom template
-    fn from_arg(value: &str) -> Result<Self, ChangelogError> {
+    pub(crate) fn from_arg(value: &str) -> Result<Self, ChangelogError> {
         match value {
             DEFAULT_TEMPLATE_NAME => Ok(TemplateKind::Default),
             REMOTE_TEMPLATE_NAME => Ok(TemplateKind::Remote),
@@ -121,11 +122,38 @@ impl TemplateKind {
 }
 
 /// A wrapper to append remote repository information to template context
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RemoteContext {
     remote: String,
     repository: String,
     owner: String,
+    platform: RemotePlatform,
+}
+
+/// The platform hosting the remote repository, used to build changelog links
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RemotePlatform {
+    GitHub,
+    GitLab,
+    Gitea,
+    Bitbucket,
+}
+
+impl RemotePlatform {
+    /// Guess the platform from the remote host name, defaults to GitHub.
+    fn from_remote(remote: &str) -> Self {
+        let remote = remote.to_lowercase();
+        if remote.contains("gitlab") {
+            RemotePlatform::GitLab
+        } else if remote.contains("bitbucket") {
+            RemotePlatform::Bitbucket
+        } else if remote.contains("gitea") || remote.contains("codeberg") {
+            RemotePlatform::Gitea
+        } else {
+            RemotePlatform::GitHub
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -171,12 +199,9 @@ impl<'a> ToContext for PackageContext<'a> {
 impl ToContext for RemoteContext {
     fn to_context(&self) -> Context {
         let mut context = tera::Context::new();
-        context.insert("platform", &format!("https://{}", self.remote.as_str()));
+        context.insert("platform", &self.base_url());
         context.insert("owner", self.owner.as_str());
-        context.insert(
-            "repository_url",
-            &format!("https://{}/{}/{}", self.remote, self.owner, self.repository),
-        );
+        context.insert("repository_url", &self.repository_url());
 
         context
     }
@@ -187,15 +212,258 @@ impl RemoteContext {
         remote: Option<String>,
         repository: Option<String>,
         owner: Option<String>,
-    ) -> Option<Self> {
+    ) -> Result<Option<Self>, ChangelogError> {
         match (remote, repository, owner) {
-            (Some(remote), Some(repository), Some(owner)) => Some(Self {
+            (Some(remote), Some(repository), Some(owner)) => Ok(Some(Self {
+                platform: RemotePlatform::from_remote(&remote),
                 remote,
                 repository,
                 owner,
-            }),
-            (None, None, None) => None,
-            _ => panic!("Changelog remote context should be set. Missing one of 'remote', 'repository', 'owner' in changelog configuration")
+            })),
+            (None, None, None) => Ok(None),
+            (remote, repository, owner) => {
+                let missing = [
+                    ("'remote'", remote),
+                    ("'repository'", repository),
+                    ("'owner'", owner),
+                ]
+                .into_iter()
+                .filter(|(_, value)| value.is_none())
+                .map(|(name, _)| name)
+                .collect();
+
+                Err(ChangelogError::IncompleteRemoteContext(missing))
+            }
         }
     }
+
+    /// Override the platform guessed from the remote host name, for self-hosted instances.
+    pub fn with_platform(mut self, platform: Option<RemotePlatform>) -> Self {
+        if let Some(platform) = platform {
+            self.platform = platform;
+        }
+
+        self
+    }
+
+    /// The remote base url, `remote` may omit the scheme, in which case https is assumed.
+    fn base_url(&self) -> String {
+        let remote = self.remote.trim_end_matches('/');
+        if remote.contains("://") {
+            remote.to_string()
+        } else {
+            format!("https://{remote}")
+        }
+    }
+
+    fn repository_url(&self) -> String {
+        format!("{}/{}/{}", self.base_url(), self.owner, self.repository)
+    }
+
+    pub(crate) fn commit_url(&self, id: &str) -> String {
+        let path = match self.platform {
+            RemotePlatform::GitHub | RemotePlatform::Gitea => "commit",
+            RemotePlatform::GitLab => "-/commit",
+            RemotePlatform::Bitbucket => "commits",
+        };
+
+        format!("{}/{path}/{id}", self.repository_url())
+    }
+
+    pub(crate) fn compare_url(&self, from: &str, to: &str) -> String {
+        let repository_url = self.repository_url();
+        match self.platform {
+            RemotePlatform::GitHub => format!("{repository_url}/compare/{from}..{to}"),
+            RemotePlatform::GitLab => format!("{repository_url}/-/compare/{from}...{to}"),
+            RemotePlatform::Gitea => format!("{repository_url}/compare/{from}...{to}"),
+            RemotePlatform::Bitbucket => {
+                format!("{repository_url}/branches/compare/{to}%0D{from}")
+            }
+        }
+    }
+
+    pub(crate) fn issue_url(&self, id: &str) -> String {
+        let path = match self.platform {
+            RemotePlatform::GitLab => "-/issues",
+            _ => "issues",
+        };
+
+        format!("{}/{path}/{id}", self.repository_url())
+    }
+
+    pub(crate) fn tag_url(&self, tag: &str) -> String {
+        let path = match self.platform {
+            RemotePlatform::GitHub => "tree",
+            RemotePlatform::GitLab => "-/tree",
+            RemotePlatform::Gitea => "src/tag",
+            RemotePlatform::Bitbucket => "src",
+        };
+
+        format!("{}/{path}/{tag}", self.repository_url())
+    }
+
+    pub(crate) fn user_url(&self, username: &str) -> String {
+        format!("{}/{username}", self.base_url())
+    }
+}
+
+/// Split a git remote url into its remote host, owner and repository name.
+pub(crate) fn parse_remote_url(url: &str) -> Option<(String, String, String)> {
+    let url = url.trim();
+    let (scheme, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (Some(scheme), rest),
+        None => (None, url),
+    };
+
+    let (authority, path) = match scheme {
+        Some(_) => rest.split_once('/')?,
+        // scp like syntax: `[user@]host:path`
+        None => rest.split_once(':')?,
+    };
+
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    if host.is_empty() {
+        return None;
+    }
+
+    let remote = match scheme {
+        Some("https") => host.to_string(),
+        Some("http") => format!("http://{host}"),
+        // Ssh ports are irrelevant to the web interface
+        _ => host.split(':').next().unwrap_or(host).to_string(),
+    };
+
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, repository) = path.rsplit_once('/')?;
+
+    if owner.is_empty() || repository.is_empty() {
+        return None;
+    }
+
+    Some((remote, owner.to_string(), repository.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use speculoos::prelude::*;
+
+    use crate::conventional::changelog::template::{
+        parse_remote_url, RemoteContext, RemotePlatform,
+    };
+
+    fn remote_context(
+        remote: &str,
+        owner: &str,
+        platform: Option<RemotePlatform>,
+    ) -> RemoteContext {
+        RemoteContext::try_new(
+            Some(remote.to_string()),
+            Some("cocogitto".to_string()),
+            Some(owner.to_string()),
+        )
+        .unwrap()
+        .unwrap()
+        .with_platform(platform)
+    }
+
+    #[test]
+    fn should_build_github_urls() {
+        // Arrange
+        let remote = remote_context("github.com", "cocogitto", None);
+
+        // Assert
+        assert_that!(remote.platform).is_equal_to(RemotePlatform::GitHub);
+        assert_that!(remote.commit_url("17f7e23"))
+            .is_equal_to("https://github.com/cocogitto/cocogitto/commit/17f7e23".to_string());
+        assert_that!(remote.compare_url("0.1.0", "1.0.0"))
+            .is_equal_to("https://github.com/cocogitto/cocogitto/compare/0.1.0..1.0.0".to_string());
+        assert_that!(remote.issue_url("12"))
+            .is_equal_to("https://github.com/cocogitto/cocogitto/issues/12".to_string());
+        assert_that!(remote.user_url("oknozor"))
+            .is_equal_to("https://github.com/oknozor".to_string());
+    }
+
+    #[test]
+    fn should_build_gitlab_urls_with_subgroups() {
+        // Arrange
+        let remote = remote_context("gitlab.com", "group/subgroup", None);
+
+        // Assert
+        assert_that!(remote.platform).is_equal_to(RemotePlatform::GitLab);
+        assert_that!(remote.commit_url("17f7e23")).is_equal_to(
+            "https://gitlab.com/group/subgroup/cocogitto/-/commit/17f7e23".to_string(),
+        );
+        assert_that!(remote.compare_url("0.1.0", "1.0.0")).is_equal_to(
+            "https://gitlab.com/group/subgroup/cocogitto/-/compare/0.1.0...1.0.0".to_string(),
+        );
+        assert_that!(remote.issue_url("12"))
+            .is_equal_to("https://gitlab.com/group/subgroup/cocogitto/-/issues/12".to_string());
+    }
+
+    #[test]
+    fn should_build_self_hosted_urls() {
+        // Arrange
+        let remote = remote_context(
+            "http://git.example.com:3000/",
+            "cocogitto",
+            Some(RemotePlatform::Gitea),
+        );
+
+        // Assert
+        assert_that!(remote.commit_url("17f7e23")).is_equal_to(
+            "http://git.example.com:3000/cocogitto/cocogitto/commit/17f7e23".to_string(),
+        );
+        assert_that!(remote.tag_url("1.0.0")).is_equal_to(
+            "http://git.example.com:3000/cocogitto/cocogitto/src/tag/1.0.0".to_string(),
+        );
+        assert_that!(remote.user_url("oknozor"))
+            .is_equal_to("http://git.example.com:3000/oknozor".to_string());
+    }
+
+    #[test]
+    fn should_build_bitbucket_urls() {
+        // Arrange
+        let remote = remote_context("bitbucket.org", "cocogitto", None);
+
+        // Assert
+        assert_that!(remote.commit_url("17f7e23"))
+            .is_equal_to("https://bitbucket.org/cocogitto/cocogitto/commits/17f7e23".to_string());
+        assert_that!(remote.compare_url("0.1.0", "1.0.0")).is_equal_to(
+            "https://bitbucket.org/cocogitto/cocogitto/branches/compare/1.0.0%0D0.1.0".to_string(),
+        );
+    }
+
+    #[test]
+    fn should_parse_remote_urls() {
+        let parsed = |url| {
+            parse_remote_url(url).map(|(remote, owner, repo)| format!("{remote} {owner} {repo}"))
+        };
+
+        assert_that!(parsed("git@github.com:cocogitto/cocogitto.git"))
+            .is_equal_to(Some("github.com cocogitto cocogitto".to_string()));
+        assert_that!(parsed("https://github.com/cocogitto/cocogitto"))
+            .is_equal_to(Some("github.com cocogitto cocogitto".to_string()));
+        assert_that!(parsed("https://user@gitlab.com/group/subgroup/repo.git/"))
+            .is_equal_to(Some("gitlab.com group/subgroup repo".to_string()));
+        assert_that!(parsed(
+            "ssh://git@gitlab.example.com:2222/group/subgroup/repo.git"
+        ))
+        .is_equal_to(Some("gitlab.example.com group/subgroup repo".to_string()));
+        assert_that!(parsed("http://git.example.com:3000/owner/repo.git"))
+            .is_equal_to(Some("http://git.example.com:3000 owner repo".to_string()));
+        assert_that!(parsed("/tmp/repo.git")).is_none();
+        assert_that!(parsed("file:///tmp/repo.git")).is_none();
+    }
+
+    #[test]
+    fn should_fail_with_incomplete_remote_context() {
+        // Act
+        let context = RemoteContext::try_new(Some("github.com".to_string()), None, None);
+
+        // Assert
+        assert_that!(context)
+            .is_err()
+            .matches(|err| err.to_string().contains("'repository', 'owner'"));
+    }
 }
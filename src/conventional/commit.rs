@@ -2,12 +2,13 @@ use std::cmp::Ordering;
 use std::fmt::{self, Formatter};
 
 use crate::conventional::error::ConventionalCommitError;
-use crate::SETTINGS;
+use crate::settings::Settings;
 use chrono::{NaiveDateTime, Utc};
 use colored::*;
-use conventional_commit_parser::commit::ConventionalCommit;
+use conventional_commit_parser::commit::{ConventionalCommit, Footer};
 use git2::Commit as Git2Commit;
 use log::info;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Eq, PartialEq)]
@@ -18,8 +19,10 @@ pub struct Commit {
     pub(crate) date: NaiveDateTime,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq)]
+/// A commit type configuration
+#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq, JsonSchema)]
 pub struct CommitConfig {
+    /// The changelog section title of this commit type
     pub changelog_title: String,
 }
 
@@ -34,6 +37,7 @@ impl CommitConfig {
 impl Commit {
     pub(crate) fn from_git_commit(
         commit: &Git2Commit,
+        settings: &Settings,
     ) -> Result<Self, Box<ConventionalCommitError>> {
         let oid = commit.id().to_string();
 
@@ -56,7 +60,7 @@ impl Commit {
                     date,
                 };
 
-                match &SETTINGS.commit_types().get(&commit.message.commit_type) {
+                match &settings.commit_types().get(&commit.message.commit_type) {
                     Some(_) => Ok(commit),
                     None => Err(Box::new(ConventionalCommitError::CommitTypeNotAllowed {
                         oid: commit.oid.to_string(),
@@ -79,6 +83,45 @@ impl Commit {
         }
     }
 
+    /// Same as [`Commit::from_git_commit`], but when `parse_squash_commits` is enabled,
+    /// conventional commits listed in the body of a squash commit (ex: GitHub's `* feat: ...`
+    /// bullet list) are returned as individual commits sharing the squash commit oid, author and date.
+    /// The squash commit header is only used when no embedded conventional commit is found,
+    /// apart from its breaking change marker and footers which are carried over to each commit.
+    pub(crate) fn from_squashed_git_commit(
+        commit: &Git2Commit,
+        settings: &Settings,
+    ) -> Result<Vec<Self>, Box<ConventionalCommitError>> {
+        if settings.parse_squash_commits {
+            let squashed = Commit::squashed_commits(commit, settings);
+            if !squashed.is_empty() {
+                return Ok(squashed);
+            }
+        }
+
+        Commit::from_git_commit(commit, settings).map(|commit| vec![commit])
+    }
+
+    fn squashed_commits(commit: &Git2Commit, settings: &Settings) -> Vec<Self> {
+        let oid = commit.id().to_string();
+        let date = NaiveDateTime::from_timestamp_opt(commit.time().seconds(), 0)
+            .expect("valid commit date");
+        let author = commit.author().name().unwrap_or("").to_string();
+        let message = commit.message().unwrap_or("");
+        let commit_types = settings.commit_types();
+
+        parse_squashed_messages(message)
+            .into_iter()
+            .filter(|message| commit_types.contains_key(&message.commit_type))
+            .map(|message| Commit {
+                oid: oid.clone(),
+                message,
+                author: author.clone(),
+                date,
+            })
+            .collect()
+    }
+
     pub(crate) fn shorthand(&self) -> &str {
         if self.oid != "not committed" {
             &self.oid[0..6]
@@ -191,6 +234,7 @@ pub fn verify(
     author: Option<String>,
     message: &str,
     ignore_merge_commit: bool,
+    settings: &Settings,
 ) -> Result<(), Box<ConventionalCommitError>> {
     // Strip away comments from git message before parsing
     let msg: String = message
@@ -209,7 +253,7 @@ pub fn verify(
     let commit = conventional_commit_parser::parse(msg);
 
     match commit {
-        Ok(commit) => match &SETTINGS.commit_types().get(&commit.commit_type) {
+        Ok(commit) => match &settings.commit_types().get(&commit.commit_type) {
             Some(_) => {
                 info!(
                     "{}",
@@ -233,6 +277,169 @@ pub fn verify(
     }
 }
 
+/// A commit that may revert another one, using git's `This reverts commit <oid>` convention.
+pub(crate) trait Revert {
+    /// The oid of this commit.
+    fn commit_oid(&self) -> String;
+
+    /// The (possibly abbreviated) oid of the commit reverted by this one, if any.
+    fn reverted_oid(&self) -> Option<&str>;
+}
+
+impl Revert for Git2Commit<'_> {
+    fn commit_oid(&self) -> String {
+        self.id().to_string()
+    }
+
+    fn reverted_oid(&self) -> Option<&str> {
+        self.message().and_then(parse_reverted_oid)
+    }
+}
+
+impl<T: Revert> Revert for &T {
+    fn commit_oid(&self) -> String {
+        (*self).commit_oid()
+    }
+
+    fn reverted_oid(&self) -> Option<&str> {
+        (*self).reverted_oid()
+    }
+}
+
+/// Drop commits reverted within the given commits, along with the commits reverting them.
+/// Commits are expected newest first (i.e. in revwalk order), so that a reverted revert
+/// does not cancel the original commit.
+pub(crate) fn drop_reverted_commits<T: Revert>(commits: Vec<T>) -> Vec<T> {
+    let oids: Vec<String> = commits.iter().map(Revert::commit_oid).collect();
+    let mut dropped = vec![false; commits.len()];
+
+    for (idx, commit) in commits.iter().enumerate() {
+        if dropped[idx] {
+            continue;
+        }
+
+        let reverted_idx = commit.reverted_oid().and_then(|reverted| {
+            oids.iter().enumerate().position(|(other, oid)| {
+                other != idx && !dropped[other] && oid.starts_with(reverted)
+            })
+        });
+
+        if let Some(reverted_idx) = reverted_idx {
+            dropped[idx] = true;
+            dropped[reverted_idx] = true;
+        }
+    }
+
+    commits
+        .into_iter()
+        .zip(dropped)
+        .filter(|(_, dropped)| !dropped)
+        .map(|(commit, _)| commit)
+        .collect()
+}
+
+fn parse_reverted_oid(message: &str) -> Option<&str> {
+    message.lines().find_map(|line| {
+        let oid = line
+            .trim()
+            .strip_prefix("This reverts commit ")?
+            .split(|char: char| !char.is_ascii_hexdigit())
+            .next()?;
+
+        (oid.len() >= 7).then_some(oid)
+    })
+}
+
+/// Parse the bullet entries (`* ` or `- `) found in a squash commit body.
+/// Lines following a bullet, up to the next one, are treated as its body and footers.
+/// Bullets that are not valid conventional commits are ignored.
+/// A breaking change marked on the squash commit header applies to every entry, while the footers
+/// ending the squash commit message belong to the last entry. Trailers such as `Co-authored-by:`
+/// are dropped.
+fn parse_squashed_messages(message: &str) -> Vec<ConventionalCommit> {
+    let mut lines = message.lines();
+    let header_breaking_change = lines
+        .next()
+        .and_then(|header| conventional_commit_parser::parse_summary(header).ok())
+        .is_some_and(|header| header.is_breaking_change);
+
+    let lines: Vec<&str> = lines.filter(|line| !is_trailer(line)).collect();
+    let (lines, footers) = split_squash_footers(&lines);
+
+    let mut entries: Vec<(&str, Vec<&str>)> = vec![];
+
+    for line in lines {
+        let bullet = line
+            .strip_prefix("* ")
+            .or_else(|| line.strip_prefix("- "))
+            .map(str::trim);
+
+        match (bullet, entries.last_mut()) {
+            (Some(header), _) => entries.push((header, vec![])),
+            (None, Some((_, body))) => body.push(line),
+            (None, None) => {}
+        }
+    }
+
+    let mut commits: Vec<ConventionalCommit> = entries
+        .into_iter()
+        .filter_map(|(header, body)| {
+            let body = body.join("\n");
+            let body = body.trim();
+            if body.is_empty() {
+                conventional_commit_parser::parse(header).ok()
+            } else {
+                conventional_commit_parser::parse(&format!("{header}\n\n{body}"))
+                    .ok()
+                    .or_else(|| conventional_commit_parser::parse(header).ok())
+            }
+        })
+        .map(|mut commit| {
+            commit.is_breaking_change |= header_breaking_change;
+            commit
+        })
+        .collect();
+
+    if let Some(last) = commits.last_mut() {
+        last.is_breaking_change |= footers.iter().any(Footer::is_breaking_change);
+        last.footers.extend(footers);
+    }
+
+    commits
+}
+
+/// Split the last paragraph of a squash commit body when it only contains footers,
+/// ex: `BREAKING CHANGE: ...` or `Refs: #12`.
+fn split_squash_footers<'a>(lines: &'a [&'a str]) -> (&'a [&'a str], Vec<Footer>) {
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |idx| idx + 1);
+    let lines = &lines[..end];
+    let start = lines
+        .iter()
+        .rposition(|line| line.trim().is_empty())
+        .map_or(0, |idx| idx + 1);
+    let paragraph = &lines[start..];
+
+    let is_bullet = |line: &&str| line.starts_with("* ") || line.starts_with("- ");
+    if start == 0 || paragraph.iter().any(is_bullet) {
+        return (lines, vec![]);
+    }
+
+    match conventional_commit_parser::parse_footers(&paragraph.join("\n")) {
+        Ok(footers) if !footers.is_empty() => (&lines[..start], footers),
+        _ => (lines, vec![]),
+    }
+}
+
+/// Whether the line is a git trailer such as `Co-authored-by:` or `Signed-off-by:`.
+fn is_trailer(line: &str) -> bool {
+    line.split_once(": ").is_some_and(|(token, _)| {
+        !token.contains(char::is_whitespace) && token.to_lowercase().ends_with("-by")
+    })
+}
+
 pub(crate) fn format_summary(commit: &ConventionalCommit) -> String {
     match &commit.scope {
         None => format!("{}: {}", commit.commit_type, commit.summary,),
@@ -244,11 +451,12 @@ pub(crate) fn format_summary(commit: &ConventionalCommit) -> String {
 
 #[cfg(test)]
 mod test {
-    use crate::conventional::commit::{format_summary, verify, Commit};
+    use crate::conventional::commit::{format_summary, parse_squashed_messages, verify, Commit};
 
     use chrono::NaiveDateTime;
     use cmd_lib::run_fun;
 
+    use crate::settings::Settings;
     use crate::Repository;
     use anyhow::Result;
     use conventional_commit_parser::commit::{CommitType, ConventionalCommit, Footer, Separator};
@@ -321,7 +529,7 @@ mod test {
         let message = "feat(database): add postgresql driver";
 
         // Act
-        let result = verify(Some("toml".into()), message, false);
+        let result = verify(Some("toml".into()), message, false, &Settings::default());
 
         // Assert
         assert_that!(result).is_ok();
@@ -339,7 +547,7 @@ mod test {
         );
 
         // Act
-        let result = verify(Some("toml".into()), message, false);
+        let result = verify(Some("toml".into()), message, false, &Settings::default());
 
         // Assert
         assert_that!(result).is_ok();
@@ -351,7 +559,7 @@ mod test {
         let message = "feat add postgresql driver";
 
         // Act
-        let result = verify(Some("toml".into()), message, false);
+        let result = verify(Some("toml".into()), message, false, &Settings::default());
 
         // Assert
         assert_that!(result).is_err();
@@ -363,7 +571,7 @@ mod test {
         let message = "post: add postgresql driver";
 
         // Act
-        let result = verify(Some("toml".into()), message, false);
+        let result = verify(Some("toml".into()), message, false, &Settings::default());
 
         // Assert
         assert_that!(result).is_err();
@@ -384,7 +592,7 @@ mod test {
             "
         );
 
-        let outcome = verify(None, message, false);
+        let outcome = verify(None, message, false, &Settings::default());
 
         assert_that!(outcome).is_ok();
         Ok(())
@@ -455,7 +663,7 @@ mod test {
         let commit = repo.0.find_commit(oid).expect("Unable to find commit");
 
         // Act
-        let commit = Commit::from_git_commit(&commit);
+        let commit = Commit::from_git_commit(&commit, repo.settings());
 
         // Assert
         assert_that!(commit).is_ok();
@@ -476,7 +684,7 @@ mod test {
         let commit = repo.0.find_commit(oid).expect("Unable to find commit");
 
         // Act
-        let commit = Commit::from_git_commit(&commit);
+        let commit = Commit::from_git_commit(&commit, repo.settings());
 
         // Assert
         assert_that!(commit).is_err();
@@ -497,9 +705,202 @@ mod test {
         let commit = repo.0.find_commit(oid).expect("Unable to find commit");
 
         // Act
-        let commit = Commit::from_git_commit(&commit);
+        let commit = Commit::from_git_commit(&commit, repo.settings());
 
         // Assert
         assert_that!(commit).is_err();
     }
+
+    #[test]
+    fn should_parse_squashed_messages() {
+        // Arrange
+        let message = indoc!(
+            "Add postgresql support (#42)
+
+            * feat(database): add postgresql driver
+
+            * fix!: remove mysql fallback
+
+            BREAKING CHANGE: mysql is no longer supported
+
+            * wip
+
+            Co-authored-by: Paul Delafosse <paul.delafosse@protonmail.com>"
+        );
+
+        // Act
+        let commits = parse_squashed_messages(message);
+
+        // Assert
+        assert_that!(commits).has_length(2);
+        assert_that!(commits[0].commit_type).is_equal_to(CommitType::Feature);
+        assert_that!(commits[0].scope).is_equal_to(Some("database".to_string()));
+        assert_that!(commits[0].summary).is_equal_to("add postgresql driver".to_string());
+        assert_that!(commits[1].commit_type).is_equal_to(CommitType::BugFix);
+        assert_that!(commits[1].is_breaking_change).is_true();
+    }
+
+    #[test]
+    fn should_carry_squash_header_breaking_change() {
+        // Arrange
+        let message = indoc!(
+            "feat!: rework the database layer (#42)
+
+            * feat(database): add postgresql driver
+
+            * fix: remove mysql fallback
+
+            Refs: #41"
+        );
+
+        // Act
+        let commits = parse_squashed_messages(message);
+
+        // Assert
+        assert_that!(commits).has_length(2);
+        assert_that!(commits.iter().all(|commit| commit.is_breaking_change)).is_true();
+        assert_that!(commits[0].footers).is_empty();
+        assert_that!(commits[1].footers).is_equal_to(vec![Footer {
+            token: "Refs".to_string(),
+            content: "#41".to_string(),
+            token_separator: Separator::Colon,
+        }]);
+        assert_that!(commits[1].body).is_none();
+    }
+
+    #[test]
+    fn should_attach_squash_footers_to_last_entry() {
+        // Arrange
+        let message = indoc!(
+            "Rework the database layer (#42)
+
+            * fix: remove mysql fallback
+
+            * feat(database): add postgresql driver
+
+            BREAKING CHANGE: mysql is no longer supported"
+        );
+
+        // Act
+        let commits = parse_squashed_messages(message);
+
+        // Assert
+        assert_that!(commits).has_length(2);
+        assert_that!(commits[0].is_breaking_change).is_false();
+        assert_that!(commits[0].footers).is_empty();
+        assert_that!(commits[1].is_breaking_change).is_true();
+        assert_that!(commits[1].footers).has_length(1);
+    }
+
+    #[test]
+    fn should_carry_squash_breaking_change_footer() {
+        // Arrange
+        let message = indoc!(
+            "Rework the database layer (#42)
+
+            * feat(database): add postgresql driver
+
+            BREAKING CHANGE: mysql is no longer supported"
+        );
+
+        // Act
+        let commits = parse_squashed_messages(message);
+
+        // Assert
+        assert_that!(commits).has_length(1);
+        assert_that!(commits[0].is_breaking_change).is_true();
+        assert_that!(commits[0].footers).has_length(1);
+    }
+
+    #[test]
+    fn should_drop_trailers_from_squashed_messages() {
+        // Arrange
+        let message = indoc!(
+            "Add postgresql support (#42)
+
+            * feat(database): add postgresql driver
+
+            * fix: remove mysql fallback
+
+            Signed-off-by: Tom <tom@cocogitto.io>
+            Co-authored-by: Paul Delafosse <paul.delafosse@protonmail.com>"
+        );
+
+        // Act
+        let commits = parse_squashed_messages(message);
+
+        // Assert
+        assert_that!(commits).has_length(2);
+        assert_that!(commits[1].body).is_none();
+        assert_that!(commits[1].footers).is_empty();
+        assert_that!(commits[1].is_breaking_change).is_false();
+    }
+
+    #[sealed_test]
+    fn should_map_squashed_commits() -> Result<()> {
+        // Arrange
+        let message = indoc!(
+            "Squashed pull request
+
+            * feat: a feature
+            * fix: a fix"
+        );
+
+        let oid = run_fun!(
+            git init;
+            git commit --allow-empty -q -m $message;
+            git log --format=%H -n 1;
+        )?;
+
+        let oid = Oid::from_str(&oid)?;
+        let repo = Repository::open(".")?;
+        let commit = repo.0.find_commit(oid)?;
+        let settings = Settings {
+            parse_squash_commits: true,
+            ..Default::default()
+        };
+
+        // Act
+        let commits = Commit::from_squashed_git_commit(&commit, &settings);
+
+        // Assert
+        assert_that!(commits)
+            .is_ok()
+            .matches(|commits| commits.iter().all(|commit| commit.oid == oid.to_string()))
+            .has_length(2);
+
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn should_not_map_squashed_commits_by_default() -> Result<()> {
+        // Arrange
+        let message = indoc!(
+            "chore: squashed pull request
+
+            * feat: a feature
+            * fix: a fix"
+        );
+
+        let oid = run_fun!(
+            git init;
+            git commit --allow-empty -q -m $message;
+            git log --format=%H -n 1;
+        )?;
+
+        let oid = Oid::from_str(&oid)?;
+        let repo = Repository::open(".")?;
+        let commit = repo.0.find_commit(oid)?;
+
+        // Act
+        let commits = Commit::from_squashed_git_commit(&commit, repo.settings());
+
+        // Assert
+        assert_that!(commits)
+            .is_ok()
+            .matches(|commits| commits[0].message.commit_type == CommitType::Chore)
+            .has_length(1);
+
+        Ok(())
+    }
 }
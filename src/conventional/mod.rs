@@ -1,5 +1,5 @@
 pub mod bump;
 pub mod changelog;
 pub mod commit;
-pub(crate) mod error;
+pub mod error;
 pub mod version;
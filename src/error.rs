@@ -2,21 +2,28 @@ use std::fmt::{self, Debug, Display, Formatter};
 
 use crate::git::oid::OidOf;
 
-use crate::conventional::error::ConventionalCommitError;
+use crate::conventional::changelog::error::ChangelogError;
+use crate::conventional::error::{BumpError as VersionError, ConventionalCommitError};
+use crate::git::error::{Git2Error, TagError};
+use crate::git::status::Statuses;
+use crate::settings::SettingError;
 use colored::*;
+use std::error::Error;
 
 #[derive(Debug)]
 pub(crate) struct CogCheckReport {
     pub from: OidOf,
+    pub to: OidOf,
     pub errors: Vec<ConventionalCommitError>,
 }
 
 impl Display for CogCheckReport {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let header = format!(
-            "\nFound {} non compliant commits in {}..HEAD:\n",
+            "\nFound {} non compliant commits in {}..{}:\n",
             self.errors.len(),
-            self.from
+            self.from,
+            self.to
         )
         .red()
         .bold();
@@ -57,3 +64,143 @@ impl Display for BumpError {
         write!(f, "{}\n{}", header, suggestion)
     }
 }
+
+/// The error returned by the [`CocoGitto`](crate::CocoGitto) library API.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CogError {
+    Git(Git2Error),
+    Settings(SettingError),
+    Tag(TagError),
+    Version(VersionError),
+    Changelog(ChangelogError),
+    /// The repository contains un-staged or un-committed changes
+    DirtyWorkingTree(Statuses),
+    /// The current branch does not match any `branch_whitelist` pattern
+    BranchNotAllowed {
+        branch: String,
+        whitelist: Vec<String>,
+    },
+    InvalidBranchPattern {
+        pattern: String,
+        err: globset::Error,
+    },
+    /// The next version is not greater than the current one
+    VersionNotGreater {
+        current: String,
+        next: String,
+    },
+    /// A hook failed before the version commit, the changes made so far were stashed
+    HookFailed {
+        version: String,
+        cause: anyhow::Error,
+    },
+    Other(anyhow::Error),
+}
+
+impl Display for CogError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CogError::Git(err) => write!(f, "{}", err),
+            CogError::Settings(err) => write!(f, "{}", err),
+            CogError::Tag(err) => write!(f, "{}", err),
+            CogError::Version(err) => write!(f, "{}", err),
+            CogError::Changelog(err) => write!(f, "{}", err),
+            CogError::DirtyWorkingTree(statuses) => write!(f, "{}", statuses),
+            CogError::BranchNotAllowed { branch, whitelist } => write!(
+                f,
+                "No patterns matched in {:?} for branch '{}', bump is not allowed",
+                whitelist, branch
+            ),
+            CogError::InvalidBranchPattern { pattern, err } => {
+                write!(f, "invalid branch_whitelist pattern '{}': {}", pattern, err)
+            }
+            CogError::VersionNotGreater { current, next } => {
+                let comparison = format!("{} <= {}", current, next).red();
+                let cause_key = "cause:".red();
+                writeln!(
+                    f,
+                    "{}:\n\t{} version MUST be greater than current one: {}",
+                    "SemVer Error".red(),
+                    cause_key,
+                    comparison
+                )
+            }
+            CogError::HookFailed { version, cause } => write!(
+                f,
+                "{}",
+                BumpError {
+                    cause: cause.to_string(),
+                    version: version.clone(),
+                    stash_number: 0,
+                }
+            ),
+            CogError::Other(err) => write!(f, "{:#}", err),
+        }
+    }
+}
+
+impl From<Git2Error> for CogError {
+    fn from(err: Git2Error) -> Self {
+        Self::Git(err)
+    }
+}
+
+impl From<SettingError> for CogError {
+    fn from(err: SettingError) -> Self {
+        Self::Settings(err)
+    }
+}
+
+impl From<TagError> for CogError {
+    fn from(err: TagError) -> Self {
+        Self::Tag(err)
+    }
+}
+
+impl From<VersionError> for CogError {
+    fn from(err: VersionError) -> Self {
+        Self::Version(err)
+    }
+}
+
+impl From<semver::Error> for CogError {
+    fn from(err: semver::Error) -> Self {
+        Self::Version(VersionError::SemVerError(err))
+    }
+}
+
+impl From<ChangelogError> for CogError {
+    fn from(err: ChangelogError) -> Self {
+        Self::Changelog(err)
+    }
+}
+
+impl From<tera::Error> for CogError {
+    fn from(err: tera::Error) -> Self {
+        Self::Changelog(ChangelogError::TeraError(err))
+    }
+}
+
+impl From<anyhow::Error> for CogError {
+    fn from(err: anyhow::Error) -> Self {
+        Self::Other(err)
+    }
+}
+
+impl Error for CogError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CogError::Git(err) => Some(err),
+            CogError::Settings(err) => Some(err),
+            CogError::Tag(err) => Some(err),
+            CogError::Version(err) => Some(err),
+            CogError::Changelog(err) => Some(err),
+            CogError::InvalidBranchPattern { err, .. } => Some(err),
+            CogError::HookFailed { cause, .. } | CogError::Other(cause) => Some(cause.as_ref()),
+            CogError::DirtyWorkingTree(_)
+            | CogError::BranchNotAllowed { .. }
+            | CogError::VersionNotGreater { .. } => None,
+        }
+    }
+}
@@ -35,6 +35,13 @@ impl Repository {
         }
     }
 
+    /// Undo the HEAD commit `oid`, its changes are kept in the index.
+    pub(crate) fn reset_commit(&self, oid: Oid) -> Result<(), Git2Error> {
+        let parent = self.0.find_commit(oid)?.parent(0)?;
+        self.0.reset(parent.as_object(), ResetType::Soft, None)?;
+        Ok(())
+    }
+
     fn commit_or_signed_commit(
         &self,
         sig: &Signature,
@@ -20,6 +20,10 @@ pub enum Git2Error {
     StashError(git2::Error),
     StatusError(git2::Error),
     CommitNotFound(git2::Error),
+    RevisionNotFound {
+        revision: String,
+        err: git2::Error,
+    },
     IOError(io::Error),
     GpgError(String),
     Other(git2::Error),
@@ -106,6 +110,9 @@ impl Display for Git2Error {
                 writeln!(f, "failed to peel git object to commit",)
             }
             Git2Error::CommitNotFound(_) => writeln!(f, "commit not found"),
+            Git2Error::RevisionNotFound { revision, .. } => {
+                writeln!(f, "revision `{}` not found", revision)
+            }
             Git2Error::CommitterNotFound => writeln!(f, "unable to get committer"),
             Git2Error::NoTagFound => writeln!(f, "no tag found"),
             Git2Error::StashError(_) => writeln!(f, "git stash failed"),
@@ -130,7 +137,8 @@ impl Display for Git2Error {
             | Git2Error::StashError(err)
             | Git2Error::StatusError(err)
             | Git2Error::Other(err)
-            | Git2Error::CommitNotFound(err) => writeln!(f, "\ncause: {}", err),
+            | Git2Error::CommitNotFound(err)
+            | Git2Error::RevisionNotFound { err, .. } => writeln!(f, "\ncause: {}", err),
             Git2Error::GpgError(err) => writeln!(f, "\ncause: {}", err),
             Git2Error::TagError(err) => writeln!(f, "\ncause: {}", err),
             Git2Error::IOError(err) => writeln!(f, "\ncause: {}", err),
@@ -139,6 +147,15 @@ impl Display for Git2Error {
     }
 }
 
+impl Git2Error {
+    pub fn revision_not_found(revision: &str, err: git2::Error) -> Self {
+        Git2Error::RevisionNotFound {
+            revision: revision.to_string(),
+            err,
+        }
+    }
+}
+
 impl Display for TagError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
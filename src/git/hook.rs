@@ -1,33 +1,56 @@
 use std::collections::HashMap;
 use std::fs::{self, Permissions};
-use std::io;
 #[cfg(target_family = "unix")]
 use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 
 use crate::{CocoGitto, HookType};
 
-use crate::settings::BumpProfile;
-use anyhow::{anyhow, Result};
+use crate::settings::{BumpProfile, HookConfig, Settings};
+use anyhow::{bail, ensure, Result};
+use itertools::Itertools;
 
 pub(crate) static PRE_PUSH_HOOK: &[u8] = include_bytes!("assets/pre-push");
 pub(crate) static PREPARE_COMMIT_HOOK: &[u8] = include_bytes!("assets/commit-msg");
-const PRE_COMMIT_HOOK_PATH: &str = ".git/hooks/commit-msg";
-const PRE_PUSH_HOOK_PATH: &str = ".git/hooks/pre-push";
+pub(crate) static PREPARE_COMMIT_MSG_HOOK: &[u8] = include_bytes!("assets/prepare-commit-msg");
+const COMMIT_MSG_HOOK_NAME: &str = "commit-msg";
+const PRE_PUSH_HOOK_NAME: &str = "pre-push";
+const PREPARE_COMMIT_MSG_HOOK_NAME: &str = "prepare-commit-msg";
+/// Marks hooks installed by cocogitto, only those can be uninstalled
+const COG_HOOK_MARKER: &str = "# Installed by cocogitto";
+/// Hooks installed by cocogitto versions predating `COG_HOOK_MARKER`
+const LEGACY_HOOKS: [&str; 2] = [
+    include_str!("assets/legacy/pre-push"),
+    include_str!("assets/legacy/commit-msg"),
+];
+/// Extension of a pre-existing hook chained before the cocogitto one
+const CHAINED_HOOK_EXTENSION: &str = "pre-cog";
+/// Line of the pre-push hook capturing the pushed refs from stdin
+const PUSHED_REFS_CAPTURE: &str = "PUSHED_REFS=$(cat)";
 
 pub trait Hooks {
     fn bump_profiles(&self) -> &HashMap<String, BumpProfile>;
-    fn pre_bump_hooks(&self) -> &Vec<String>;
-    fn post_bump_hooks(&self) -> &Vec<String>;
-
-    fn get_hooks(&self, hook_type: HookType) -> &Vec<String> {
+    fn pre_bump_hooks(&self) -> &Vec<HookConfig>;
+    fn post_bump_hooks(&self) -> &Vec<HookConfig>;
+    fn pre_changelog_hooks(&self) -> &Vec<HookConfig>;
+    fn post_changelog_hooks(&self) -> &Vec<HookConfig>;
+    fn pre_commit_hooks(&self) -> &Vec<HookConfig>;
+    fn pre_tag_hooks(&self) -> &Vec<HookConfig>;
+    fn on_failure_hooks(&self) -> &Vec<HookConfig>;
+
+    fn get_hooks(&self, hook_type: HookType) -> &Vec<HookConfig> {
         match hook_type {
             HookType::PreBump => self.pre_bump_hooks(),
             HookType::PostBump => self.post_bump_hooks(),
+            HookType::PreChangelog => self.pre_changelog_hooks(),
+            HookType::PostChangelog => self.post_changelog_hooks(),
+            HookType::PreCommit => self.pre_commit_hooks(),
+            HookType::PreTag => self.pre_tag_hooks(),
+            HookType::OnFailure => self.on_failure_hooks(),
         }
     }
 
-    fn get_profile_hooks(&self, profile: &str, hook_type: HookType) -> &Vec<String> {
+    fn get_profile_hooks(&self, profile: &str, hook_type: HookType) -> &Vec<HookConfig> {
         let profile = self
             .bump_profiles()
             .get(profile)
@@ -35,45 +58,188 @@ pub trait Hooks {
         match hook_type {
             HookType::PreBump => &profile.pre_bump_hooks,
             HookType::PostBump => &profile.post_bump_hooks,
+            HookType::PreChangelog => &profile.pre_changelog_hooks,
+            HookType::PostChangelog => &profile.post_changelog_hooks,
+            HookType::PreCommit => &profile.pre_commit_hooks,
+            HookType::PreTag => &profile.pre_tag_hooks,
+            HookType::OnFailure => &profile.on_failure_hooks,
         }
     }
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum HookKind {
     PrepareCommit,
     PrePush,
+    PrepareCommitMsg,
     All,
 }
 
+/// What to do when a hook not installed by cocogitto already exists.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum ExistingHook {
+    /// Fail without touching the existing hook
+    #[default]
+    Refuse,
+    /// Replace the existing hook
+    Overwrite,
+    /// Keep the existing hook and run it before the cocogitto one
+    Chain,
+}
+
+impl HookKind {
+    fn kinds(self) -> Vec<HookKind> {
+        match self {
+            HookKind::All => vec![HookKind::PrepareCommit, HookKind::PrePush],
+            kind => vec![kind],
+        }
+    }
+
+    fn file_name(&self) -> &'static str {
+        match self {
+            HookKind::PrepareCommit => COMMIT_MSG_HOOK_NAME,
+            HookKind::PrePush => PRE_PUSH_HOOK_NAME,
+            HookKind::PrepareCommitMsg => PREPARE_COMMIT_MSG_HOOK_NAME,
+            HookKind::All => unreachable!(),
+        }
+    }
+
+    fn content(&self, settings: &Settings) -> String {
+        match self {
+            HookKind::PrepareCommit => String::from_utf8_lossy(PREPARE_COMMIT_HOOK).to_string(),
+            HookKind::PrePush => String::from_utf8_lossy(PRE_PUSH_HOOK).to_string(),
+            HookKind::PrepareCommitMsg => {
+                let commit_types = settings
+                    .commit_types()
+                    .keys()
+                    .map(|commit_type| commit_type.as_ref())
+                    .sorted()
+                    .join(", ");
+                String::from_utf8_lossy(PREPARE_COMMIT_MSG_HOOK)
+                    .replace("{{commit_types}}", &commit_types)
+            }
+            HookKind::All => unreachable!(),
+        }
+    }
+
+    /// The snippet running a chained hook, and the line of the hook it must be inserted after.
+    /// The pre-push hook reads the pushed refs from stdin, so they are captured first
+    /// and handed over to the chained hook.
+    fn chain(&self) -> (&'static str, String) {
+        match self {
+            HookKind::PrePush => (
+                PUSHED_REFS_CAPTURE,
+                format!(
+                    "\nif [ -x \"$0.{CHAINED_HOOK_EXTENSION}\" ]; then\n    printf '%s\\n' \"$PUSHED_REFS\" | \"$0.{CHAINED_HOOK_EXTENSION}\" \"$@\" || exit $?\nfi\n"
+                ),
+            ),
+            _ => (
+                COG_HOOK_MARKER,
+                format!(
+                    "\nif [ -x \"$0.{CHAINED_HOOK_EXTENSION}\" ]; then\n    \"$0.{CHAINED_HOOK_EXTENSION}\" \"$@\" || exit $?\nfi\n"
+                ),
+            ),
+        }
+    }
+}
+
 impl CocoGitto {
-    pub fn install_hook(&self, kind: HookKind) -> Result<()> {
-        let repodir = &self
-            .repository
-            .get_repo_dir()
-            .ok_or_else(|| anyhow!("Repository root directory not found"))?
-            .to_path_buf();
-
-        match kind {
-            HookKind::PrepareCommit => create_hook(repodir, HookKind::PrepareCommit)?,
-            HookKind::PrePush => create_hook(repodir, HookKind::PrePush)?,
-            HookKind::All => {
-                create_hook(repodir, HookKind::PrepareCommit)?;
-                create_hook(repodir, HookKind::PrePush)?
+    pub fn install_hook(&self, kind: HookKind, existing: ExistingHook) -> Result<()> {
+        let hooks_dir = self.repository.get_hooks_dir()?;
+        fs::create_dir_all(&hooks_dir)?;
+
+        for kind in kind.kinds() {
+            create_hook(&hooks_dir, kind, existing, self.settings())?;
+        }
+
+        Ok(())
+    }
+
+    /// Remove the cocogitto hooks, restoring the hooks chained during installation.
+    /// Every hook is checked before the first one is removed. With [`HookKind::All`],
+    /// hooks that are not installed are skipped.
+    pub fn uninstall_hook(&self, kind: HookKind) -> Result<()> {
+        let hooks_dir = self.repository.get_hooks_dir()?;
+        let mut installed = vec![];
+
+        for hook_kind in kind.kinds() {
+            let hook_path = hooks_dir.join(hook_kind.file_name());
+            if kind == HookKind::All && !hook_path.exists() {
+                continue;
             }
-        };
+
+            ensure!(
+                is_cog_hook(&hook_path),
+                "No {} hook installed by cocogitto in {}",
+                hook_kind.file_name(),
+                hooks_dir.display()
+            );
+
+            installed.push(hook_kind);
+        }
+
+        ensure!(
+            !installed.is_empty(),
+            "No hook installed by cocogitto in {}",
+            hooks_dir.display()
+        );
+
+        for kind in installed {
+            remove_hook(&hooks_dir, kind)?;
+        }
 
         Ok(())
     }
 }
 
-fn create_hook(path: &Path, kind: HookKind) -> io::Result<()> {
-    let (hook_path, hook_content) = match kind {
-        HookKind::PrepareCommit => (path.join(PRE_COMMIT_HOOK_PATH), PREPARE_COMMIT_HOOK),
-        HookKind::PrePush => (path.join(PRE_PUSH_HOOK_PATH), PRE_PUSH_HOOK),
-        HookKind::All => unreachable!(),
-    };
+fn is_cog_hook(hook_path: &Path) -> bool {
+    fs::read_to_string(hook_path)
+        .map(|content| {
+            content.contains(COG_HOOK_MARKER)
+                || LEGACY_HOOKS
+                    .iter()
+                    .any(|legacy| content.trim_end() == legacy.trim_end())
+        })
+        .unwrap_or(false)
+}
+
+fn create_hook(
+    hooks_dir: &Path,
+    kind: HookKind,
+    existing: ExistingHook,
+    settings: &Settings,
+) -> Result<()> {
+    let hook_path = hooks_dir.join(kind.file_name());
+    let chained_hook_path = hook_path.with_extension(CHAINED_HOOK_EXTENSION);
+
+    if hook_path.exists() && !is_cog_hook(&hook_path) {
+        match existing {
+            ExistingHook::Refuse => bail!(
+                "A {} hook already exists in {}, use --overwrite to replace it or --chain to run it before the cocogitto hook",
+                kind.file_name(),
+                hooks_dir.display()
+            ),
+            ExistingHook::Overwrite => {}
+            ExistingHook::Chain => {
+                ensure!(
+                    !chained_hook_path.exists(),
+                    "Cannot chain the existing {} hook, {} already exists",
+                    kind.file_name(),
+                    chained_hook_path.display()
+                );
+                fs::rename(&hook_path, &chained_hook_path)?;
+            }
+        }
+    }
+
+    let mut content = kind.content(settings);
+    if chained_hook_path.exists() {
+        let (anchor, chain) = kind.chain();
+        let idx = content.find(anchor).expect("hook chain anchor") + anchor.len();
+        content.insert_str(idx + 1, &chain);
+    }
 
-    fs::write(&hook_path, hook_content)?;
+    fs::write(&hook_path, content)?;
 
     #[cfg(target_family = "unix")]
     {
@@ -84,17 +250,32 @@ fn create_hook(path: &Path, kind: HookKind) -> io::Result<()> {
     Ok(())
 }
 
+fn remove_hook(hooks_dir: &Path, kind: HookKind) -> Result<()> {
+    let hook_path = hooks_dir.join(kind.file_name());
+    let chained_hook_path = hook_path.with_extension(CHAINED_HOOK_EXTENSION);
+
+    fs::remove_file(&hook_path)?;
+
+    // Restore the hook chained during installation
+    if chained_hook_path.exists() {
+        fs::rename(&chained_hook_path, &hook_path)?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs::File;
 
-    use crate::git::hook::HookKind;
+    use crate::git::hook::{ExistingHook, HookKind};
     use crate::CocoGitto;
 
     use anyhow::Result;
-    use cmd_lib::run_cmd;
+    use cmd_lib::{run_cmd, run_fun};
     use sealed_test::prelude::*;
     use speculoos::prelude::*;
+    use std::fs;
     use std::path::Path;
 
     #[sealed_test]
@@ -105,7 +286,7 @@ mod tests {
         let cog = CocoGitto::get()?;
 
         // Act
-        cog.install_hook(HookKind::PrepareCommit)?;
+        cog.install_hook(HookKind::PrepareCommit, ExistingHook::Refuse)?;
 
         // Assert
         assert_that!(Path::new(".git/hooks/commit-msg")).exists();
@@ -121,7 +302,7 @@ mod tests {
         let cog = CocoGitto::get()?;
 
         // Act
-        cog.install_hook(HookKind::PrePush)?;
+        cog.install_hook(HookKind::PrePush, ExistingHook::Refuse)?;
 
         // Assert
         assert_that!(Path::new(".git/hooks/pre-push")).exists();
@@ -137,7 +318,7 @@ mod tests {
         let cog = CocoGitto::get()?;
 
         // Act
-        cog.install_hook(HookKind::All)?;
+        cog.install_hook(HookKind::All, ExistingHook::Refuse)?;
 
         // Assert
         assert_that!(Path::new(".git/hooks/pre-push")).exists();
@@ -155,7 +336,7 @@ mod tests {
         let cog = CocoGitto::get()?;
 
         // Act
-        cog.install_hook(HookKind::PrePush)?;
+        cog.install_hook(HookKind::PrePush, ExistingHook::Refuse)?;
 
         // Assert
         let prepush = File::open(".git/hooks/pre-push")?;
@@ -164,4 +345,231 @@ mod tests {
         assert_that!(metadata.permissions().mode() & 0o777).is_equal_to(0o755);
         Ok(())
     }
+
+    #[sealed_test]
+    fn add_prepare_commit_msg_hook() -> Result<()> {
+        // Arrange
+        run_cmd!(git init)?;
+
+        let cog = CocoGitto::get()?;
+
+        // Act
+        cog.install_hook(HookKind::PrepareCommitMsg, ExistingHook::Refuse)?;
+
+        // Assert
+        let hook = fs::read_to_string(".git/hooks/prepare-commit-msg")?;
+        assert_that!(hook.as_str()).contains("# Available types: build, chore, ci, docs, feat");
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn install_hook_respects_core_hooks_path() -> Result<()> {
+        // Arrange
+        run_cmd!(
+            git init;
+            git config core.hooksPath .githooks;
+        )?;
+
+        let cog = CocoGitto::get()?;
+
+        // Act
+        cog.install_hook(HookKind::PrePush, ExistingHook::Refuse)?;
+
+        // Assert
+        assert_that!(Path::new(".githooks/pre-push")).exists();
+        assert_that!(Path::new(".git/hooks/pre-push")).does_not_exist();
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn install_hook_in_worktree_uses_main_repository_hooks() -> Result<()> {
+        // Arrange
+        run_cmd!(
+            git init repo;
+            cd repo;
+            git commit --allow-empty -m "chore: init";
+            git worktree add ../worktree;
+        )?;
+        std::env::set_current_dir("worktree")?;
+
+        let cog = CocoGitto::get()?;
+
+        // Act
+        cog.install_hook(HookKind::PrePush, ExistingHook::Refuse)?;
+
+        // Assert
+        assert_that!(Path::new("../repo/.git/hooks/pre-push")).exists();
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn install_hook_refuses_to_replace_existing_hook() -> Result<()> {
+        // Arrange
+        run_cmd!(
+            git init;
+            echo "#!/bin/sh" > .git/hooks/pre-push;
+        )?;
+
+        let cog = CocoGitto::get()?;
+
+        // Act
+        let result = cog.install_hook(HookKind::PrePush, ExistingHook::Refuse);
+
+        // Assert
+        assert_that!(result).is_err();
+        assert_that!(fs::read_to_string(".git/hooks/pre-push")?.as_str())
+            .is_equal_to("#!/bin/sh\n");
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn install_hook_overwrites_existing_hook() -> Result<()> {
+        // Arrange
+        run_cmd!(
+            git init;
+            echo "#!/bin/sh" > .git/hooks/pre-push;
+        )?;
+
+        let cog = CocoGitto::get()?;
+
+        // Act
+        cog.install_hook(HookKind::PrePush, ExistingHook::Overwrite)?;
+
+        // Assert
+        let hook = fs::read_to_string(".git/hooks/pre-push")?;
+        assert_that!(hook.as_str()).contains("cog check");
+        assert_that!(Path::new(".git/hooks/pre-push.pre-cog")).does_not_exist();
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn chained_pre_push_hook_receives_pushed_refs() -> Result<()> {
+        // Arrange
+        run_cmd!(
+            git init;
+            echo "#!/bin/sh" > .git/hooks/pre-push;
+            echo "cat > pushed_refs" >> .git/hooks/pre-push;
+            chmod +x .git/hooks/pre-push;
+        )?;
+
+        let cog = CocoGitto::get()?;
+        let zero_sha = "0".repeat(40);
+        let deleted_ref = format!("(delete) {zero_sha} refs/heads/old {zero_sha}");
+
+        // Act
+        cog.install_hook(HookKind::PrePush, ExistingHook::Chain)?;
+        run_cmd!(echo $deleted_ref | .git/hooks/pre-push origin url;)?;
+
+        // Assert
+        assert_that!(fs::read_to_string("pushed_refs")?).is_equal_to(format!("{deleted_ref}\n"));
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn chain_and_uninstall_hook() -> Result<()> {
+        // Arrange
+        run_cmd!(
+            git init;
+            git config --local user.name Tom;
+            git config --local user.email toml.bombadil@themail.org;
+            echo "#!/bin/sh" > .git/hooks/prepare-commit-msg;
+            echo "touch chained" >> .git/hooks/prepare-commit-msg;
+            chmod +x .git/hooks/prepare-commit-msg;
+        )?;
+
+        let cog = CocoGitto::get()?;
+
+        // Act
+        cog.install_hook(HookKind::PrepareCommitMsg, ExistingHook::Chain)?;
+        run_cmd!(git commit --allow-empty -m "chore: chained hook";)?;
+
+        // Assert
+        assert_that!(Path::new("chained")).exists();
+        let log = run_fun!(git log --format=%s)?;
+        assert_that!(log.as_str()).is_equal_to("chore: chained hook");
+
+        // Act
+        cog.uninstall_hook(HookKind::PrepareCommitMsg)?;
+
+        // Assert
+        let hook = fs::read_to_string(".git/hooks/prepare-commit-msg")?;
+        assert_that!(hook.as_str()).is_equal_to("#!/bin/sh\ntouch chained\n");
+        assert_that!(Path::new(".git/hooks/prepare-commit-msg.pre-cog")).does_not_exist();
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn uninstall_hook_not_installed_by_cog_is_err() -> Result<()> {
+        // Arrange
+        run_cmd!(
+            git init;
+            echo "#!/bin/sh" > .git/hooks/pre-push;
+        )?;
+
+        let cog = CocoGitto::get()?;
+
+        // Act
+        let result = cog.uninstall_hook(HookKind::PrePush);
+
+        // Assert
+        assert_that!(result).is_err();
+        assert_that!(Path::new(".git/hooks/pre-push")).exists();
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn reinstall_over_legacy_hook() -> Result<()> {
+        // Arrange
+        run_cmd!(git init)?;
+        fs::write(
+            ".git/hooks/pre-push",
+            include_str!("assets/legacy/pre-push"),
+        )?;
+
+        let cog = CocoGitto::get()?;
+
+        // Act
+        let result = cog.install_hook(HookKind::All, ExistingHook::Refuse);
+
+        // Assert
+        assert_that!(result).is_ok();
+        let hook = fs::read_to_string(".git/hooks/pre-push")?;
+        assert_that!(hook.as_str()).contains("# Installed by cocogitto");
+        assert_that!(Path::new(".git/hooks/pre-push.pre-cog")).does_not_exist();
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn uninstall_all_hooks_skips_missing_hooks() -> Result<()> {
+        // Arrange
+        run_cmd!(git init)?;
+        let cog = CocoGitto::get()?;
+        cog.install_hook(HookKind::PrePush, ExistingHook::Refuse)?;
+
+        // Act
+        let result = cog.uninstall_hook(HookKind::All);
+
+        // Assert
+        assert_that!(result).is_ok();
+        assert_that!(Path::new(".git/hooks/pre-push")).does_not_exist();
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn uninstall_all_hooks_removes_nothing_on_foreign_hook() -> Result<()> {
+        // Arrange
+        run_cmd!(git init)?;
+        let cog = CocoGitto::get()?;
+        cog.install_hook(HookKind::PrePush, ExistingHook::Refuse)?;
+        run_cmd!(echo "#!/bin/sh" > .git/hooks/commit-msg)?;
+
+        // Act
+        let result = cog.uninstall_hook(HookKind::All);
+
+        // Assert
+        assert_that!(result).is_err();
+        assert_that!(Path::new(".git/hooks/pre-push")).exists();
+        assert_that!(Path::new(".git/hooks/commit-msg")).exists();
+        Ok(())
+    }
 }
@@ -21,6 +21,7 @@ impl Repository {
 
 #[cfg(test)]
 mod test {
+    use crate::settings::Settings;
     use crate::{Repository, RevspecPattern};
     use anyhow::Result;
     use cmd_lib::run_cmd;
@@ -42,14 +43,15 @@ mod test {
             changelog_path = \"two/CHANGELOG.md\"
             "
         );
+        let settings: Settings = toml::from_str(&settings)?;
 
         run_cmd!(
             git init -b master;
-            echo $settings > cog.toml;
+            echo init > README;
             git add .;
         )?;
 
-        let repo = Repository::open(".")?;
+        let repo = Repository::open(".")?.with_settings(settings);
         repo.commit("chore: init", false)?;
 
         run_cmd!(
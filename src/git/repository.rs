@@ -1,12 +1,15 @@
 use std::fmt::{Debug, Formatter};
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use crate::git::error::Git2Error;
+use crate::settings::Settings;
 use git2::{
     Commit as Git2Commit, IndexAddOption, Object, ObjectType, Oid, Repository as Git2Repository,
 };
 
-pub(crate) struct Repository(pub(crate) Git2Repository);
+/// A git repository along with the cocogitto settings used to read its tags and commits.
+pub(crate) struct Repository(pub(crate) Git2Repository, Settings);
 
 impl Repository {
     pub(crate) fn signin_key(&self) -> Result<String, Git2Error> {
@@ -22,18 +25,60 @@ impl Repository {
     pub(crate) fn init<S: AsRef<Path> + ?Sized>(path: &S) -> Result<Repository, Git2Error> {
         let repository =
             Git2Repository::init(path).map_err(Git2Error::FailedToInitializeRepository)?;
-        Ok(Repository(repository))
+        Ok(Repository(repository, Settings::default()))
     }
 
     pub(crate) fn open<S: AsRef<Path> + ?Sized>(path: &S) -> Result<Repository, Git2Error> {
         let repo = Git2Repository::discover(path).map_err(Git2Error::FailedToOpenRepository)?;
-        Ok(Repository(repo))
+        Ok(Repository(repo, Settings::default()))
+    }
+
+    /// Use the given settings instead of the default ones
+    pub(crate) fn with_settings(mut self, mut settings: Settings) -> Self {
+        settings.infer_remote(&self);
+        self.1 = settings;
+        self
+    }
+
+    pub(crate) fn settings(&self) -> &Settings {
+        &self.1
     }
 
     pub(crate) fn get_repo_dir(&self) -> Option<&Path> {
         self.0.workdir()
     }
 
+    /// Resolve a path relative to the repository working directory
+    pub(crate) fn resolve_path(&self, path: &Path) -> PathBuf {
+        match self.get_repo_dir() {
+            Some(repo_dir) => repo_dir.join(path),
+            None => path.to_path_buf(),
+        }
+    }
+
+    /// The directory where git looks up hooks, honoring `core.hooksPath`.
+    /// Worktrees share the hooks of the main repository.
+    pub(crate) fn get_hooks_dir(&self) -> Result<PathBuf, Git2Error> {
+        let config = self.0.config()?;
+        if let Ok(hooks_path) = config.get_path("core.hooksPath") {
+            return Ok(match self.get_repo_dir() {
+                Some(workdir) if hooks_path.is_relative() => workdir.join(hooks_path),
+                _ => hooks_path,
+            });
+        }
+
+        Ok(self.get_common_dir().join("hooks"))
+    }
+
+    // A linked worktree git dir contains a `commondir` file pointing to the main git dir
+    fn get_common_dir(&self) -> PathBuf {
+        let git_dir = self.0.path();
+        match fs::read_to_string(git_dir.join("commondir")) {
+            Ok(common_dir) => git_dir.join(common_dir.trim()),
+            Err(_) => git_dir.to_path_buf(),
+        }
+    }
+
     pub(crate) fn add_all(&self) -> Result<(), Git2Error> {
         let mut index = self.0.index()?;
         index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
@@ -74,6 +119,13 @@ impl Repository {
             .and_then(|head| head.shorthand().map(|shorthand| shorthand.to_string()))
     }
 
+    pub(crate) fn get_remote_url(&self, name: &str) -> Option<String> {
+        self.0
+            .find_remote(name)
+            .ok()
+            .and_then(|remote| remote.url().map(|url| url.to_string()))
+    }
+
     pub(crate) fn get_author(&self) -> Result<String, Git2Error> {
         self.0
             .signature()?
@@ -265,4 +317,18 @@ mod test {
         assert_that!(shorthand).is_equal_to(Some("master".to_string()));
         Ok(())
     }
+
+    #[sealed_test]
+    fn get_remote_url() -> Result<()> {
+        // Arrange
+        let repo = Repository::init(".")?;
+        run_cmd!(git remote add origin git@github.com:cocogitto/cocogitto.git)?;
+
+        // Act
+        let url = repo.get_remote_url("origin");
+
+        // Assert
+        assert_that!(url).is_equal_to(Some("git@github.com:cocogitto/cocogitto.git".to_string()));
+        Ok(())
+    }
 }
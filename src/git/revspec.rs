@@ -1,6 +1,7 @@
 use std::fmt;
 use std::fmt::Formatter;
 
+use chrono::NaiveDateTime;
 use git2::{Commit, ErrorCode, Oid};
 
 use crate::conventional::changelog::release::Release;
@@ -8,16 +9,17 @@ use crate::git::error::Git2Error;
 use crate::git::oid::OidOf;
 use crate::git::repository::Repository;
 use crate::git::tag::Tag;
-use crate::SETTINGS;
 
 #[derive(Debug)]
 pub struct CommitRange<'repo> {
     pub from: OidOf,
     pub to: OidOf,
+    /// The commit date of `to` when it is a tag, `None` for a release to come
+    pub date: Option<NaiveDateTime>,
     pub commits: Vec<Commit<'repo>>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct RevspecPattern {
     from: Option<String>,
     to: Option<String>,
@@ -96,12 +98,17 @@ impl Repository {
             .map(OidOf::Other)
             .expect("No commit found");
 
-        Ok(CommitRange { from, to, commits })
+        Ok(CommitRange {
+            from,
+            to,
+            date: None,
+            commits,
+        })
     }
 
     pub(crate) fn get_release_range(&self, pattern: RevspecPattern) -> Result<Release, Git2Error> {
         let target = if let Some(target) = pattern.from {
-            self.resolve_oid_of(&target)
+            self.resolve_oid_of(&target)?
         } else {
             OidOf::Other(self.get_first_commit()?)
         };
@@ -112,7 +119,7 @@ impl Repository {
         };
 
         let range = self.get_commit_range(&pattern)?;
-        let release = Release::from(range);
+        let release = Release::from_commit_range(range, self.settings());
 
         let mut release = if !release.contains_oid(target.oid()) {
             self.populate_previous_release(release, target.oid())?
@@ -143,14 +150,14 @@ impl Repository {
         // We have reached the `from` target commit
         else if target_in_range {
             if range.from != range.to {
-                let previous = Release::from(range);
+                let previous = Release::from_commit_range(range, self.settings());
                 release.previous = Some(Box::new(previous));
             }
 
             return Ok(release);
         }
 
-        let previous = Release::from(range);
+        let previous = Release::from_commit_range(range, self.settings());
         let previous = self.populate_previous_release(previous, target)?;
         release.previous = Some(Box::new(previous));
 
@@ -180,7 +187,11 @@ impl Repository {
         // get/validate the target oid
         let to = match to {
             None => self.get_head_commit_oid()?,
-            Some(to) => self.0.revparse_single(to)?.id(),
+            Some(to) => self
+                .0
+                .revparse_single(to)
+                .map_err(|err| Git2Error::revision_not_found(to, err))?
+                .id(),
         };
 
         // Either user input, latest tag since `to`, or first commit
@@ -196,11 +207,21 @@ impl Repository {
                         .expect("No commit found")
                 }),
             // We might have a tag
-            Some(from) => self.resolve_oid_of(from),
+            Some(from) => self.resolve_oid_of(from)?,
         };
 
         // Resolve shorthands and tags
         let spec = format!("{}..{}", from, to);
+        // Released versions are dated by their tagged commit
+        let date = match maybe_to_tag {
+            Some(_) => {
+                let commit = self.0.find_object(to, None)?.peel_to_commit()?;
+                let seconds = commit.time().seconds();
+                NaiveDateTime::from_timestamp_opt(seconds, 0)
+            }
+            None => None,
+        };
+
         // Attempt to resolve tag names, fallback to oid
         let to = maybe_to_tag
             .map(OidOf::Tag)
@@ -208,7 +229,12 @@ impl Repository {
 
         let commits = self.get_commit_range_from_spec(&spec)?;
 
-        Ok(CommitRange { from, to, commits })
+        Ok(CommitRange {
+            from,
+            to,
+            date,
+            commits,
+        })
     }
 
     pub fn get_commit_range_for_package(
@@ -218,7 +244,11 @@ impl Repository {
     ) -> Result<CommitRange, Git2Error> {
         let mut commit_range = self.get_commit_range(pattern)?;
         let mut commits = vec![];
-        let package = SETTINGS.packages.get(package).expect("package exists");
+        let package = self
+            .settings()
+            .packages
+            .get(package)
+            .expect("package exists");
         for commit in commit_range.commits {
             let parent = commit.parent(0).ok().map(|commit| commit.id().to_string());
 
@@ -265,7 +295,8 @@ impl Repository {
     ) -> Result<CommitRange, Git2Error> {
         let mut commit_range = self.get_commit_range(pattern)?;
         let mut commits = vec![];
-        let package_paths: Vec<_> = SETTINGS
+        let package_paths: Vec<_> = self
+            .settings()
             .packages
             .values()
             .map(|package| &package.path)
@@ -304,26 +335,28 @@ impl Repository {
         Ok(commit_range)
     }
 
-    fn resolve_oid_of(&self, from: &str) -> OidOf {
+    fn resolve_oid_of(&self, from: &str) -> Result<OidOf, Git2Error> {
         // either we have a tag name
-        self.resolve_tag(from)
-            .map(OidOf::Tag)
-            // Or an oid
-            .unwrap_or_else(|_| {
-                let object = self.0.revparse_single(from).expect("Expected oid or tag");
-
-                // Is the oid pointing to a tag ?
-                let tag = self
-                    .all_tags()
-                    .expect("Error trying to get repository tags")
-                    .into_iter()
-                    .find(|tag| *tag.oid_unchecked() == object.id());
-
-                match tag {
-                    None => OidOf::Other(object.id()),
-                    Some(tag) => OidOf::Tag(tag),
-                }
-            })
+        if let Ok(tag) = self.resolve_tag(from) {
+            return Ok(OidOf::Tag(tag));
+        }
+
+        // Or an oid
+        let object = self
+            .0
+            .revparse_single(from)
+            .map_err(|err| Git2Error::revision_not_found(from, err))?;
+
+        // Is the oid pointing to a tag ?
+        let tag = self
+            .all_tags()?
+            .into_iter()
+            .find(|tag| *tag.oid_unchecked() == object.id());
+
+        Ok(match tag {
+            None => OidOf::Other(object.id()),
+            Some(tag) => OidOf::Tag(tag),
+        })
     }
 
     fn get_commit_range_from_spec(&self, spec: &str) -> Result<Vec<Commit>, Git2Error> {
@@ -375,7 +408,7 @@ impl Repository {
                 };
 
                 if range.contains(&oid) {
-                    if let Ok(tag) = Tag::from_str(name, Some(oid)) {
+                    if let Ok(tag) = Tag::from_str(name, Some(oid), self.settings()) {
                         tags.push(tag);
                     };
                 };
@@ -531,7 +564,6 @@ mod test {
     #[sealed_test]
     fn get_package_commit_range() -> Result<()> {
         // Arrange
-        let repo = Repository::init(".")?;
         let mut packages = HashMap::new();
         packages.insert(
             "one".to_string(),
@@ -546,11 +578,10 @@ mod test {
             ..Default::default()
         };
 
-        let settings = toml::to_string(&settings)?;
+        let repo = Repository::init(".")?.with_settings(settings);
 
         run_cmd!(
-            git init;
-            echo $settings > cog.toml;
+            echo init > README;
             git add .;
             git commit -m "chore: First commit";
             mkdir one;
@@ -606,9 +637,9 @@ mod test {
         // Arrange
         let repo = Repository::open(COCOGITTO_REPOSITORY)?;
         let v1_0_0 = Oid::from_str("549070fa99986b059cbaa9457b6b6f065bbec46b")?;
-        let v1_0_0 = OidOf::Tag(Tag::from_str("1.0.0", Some(v1_0_0))?);
+        let v1_0_0 = OidOf::Tag(Tag::from_str("1.0.0", Some(v1_0_0), repo.settings())?);
         let v3_0_0 = Oid::from_str("c6508e243e2816e2d2f58828ee0c6721502958dd")?;
-        let v3_0_0 = OidOf::Tag(Tag::from_str("3.0.0", Some(v3_0_0))?);
+        let v3_0_0 = OidOf::Tag(Tag::from_str("3.0.0", Some(v3_0_0), repo.settings())?);
 
         // Act
         let range = repo.get_commit_range(&RevspecPattern::from("1.0.0..3.0.0"))?;
@@ -636,7 +667,7 @@ mod test {
         };
 
         let v1_0_0 = Oid::from_str("549070fa99986b059cbaa9457b6b6f065bbec46b")?;
-        let v1_0_0 = OidOf::Tag(Tag::from_str("1.0.0", Some(v1_0_0))?);
+        let v1_0_0 = OidOf::Tag(Tag::from_str("1.0.0", Some(v1_0_0), repo.settings())?);
 
         // Act
         let range = repo.get_commit_range(&RevspecPattern::from("1.0.0.."))?;
@@ -679,9 +710,9 @@ mod test {
         // Arrange
         let repo = Repository::open(COCOGITTO_REPOSITORY)?;
         let v2_1_1 = Oid::from_str("9dcf728d2eef6b5986633dd52ecbe9e416234898")?;
-        let v2_1_1 = OidOf::Tag(Tag::from_str("2.1.1", Some(v2_1_1))?);
+        let v2_1_1 = OidOf::Tag(Tag::from_str("2.1.1", Some(v2_1_1), repo.settings())?);
         let v3_0_0 = Oid::from_str("c6508e243e2816e2d2f58828ee0c6721502958dd")?;
-        let v3_0_0 = OidOf::Tag(Tag::from_str("3.0.0", Some(v3_0_0))?);
+        let v3_0_0 = OidOf::Tag(Tag::from_str("3.0.0", Some(v3_0_0), repo.settings())?);
 
         // Act
         let range = repo.get_commit_range(&RevspecPattern::from("..3.0.0"))?;
@@ -1,12 +1,15 @@
 use crate::git::error::Git2Error;
 use crate::git::repository::Repository;
 use crate::Tag;
+use git2::Repository as Git2Repository;
 
 impl Repository {
-    pub(crate) fn stash_failed_version(&mut self, tag: Tag) -> Result<(), Git2Error> {
+    pub(crate) fn stash_failed_version(&self, tag: Tag) -> Result<(), Git2Error> {
         let sig = self.0.signature()?;
         let message = &format!("cog_bump_{}", tag);
-        self.0
+        // Stashing needs a mutable handle, use a new one so the repository settings can stay borrowed
+        let mut repository = Git2Repository::open(self.0.path())?;
+        repository
             .stash_save(&sig, message, None)
             .map(|_| ())
             .map_err(Git2Error::StashError)
@@ -24,7 +27,7 @@ mod test {
 
     #[sealed_test]
     fn should_stash_failed_bump() -> Result<()> {
-        let mut repo = Repository::init(".")?;
+        let repo = Repository::init(".")?;
         run_cmd!(git commit -m "Initial commit" --allow-empty;)?;
 
         let statuses = repo.get_statuses()?.0;
@@ -37,7 +40,7 @@ mod test {
         let statuses = repo.get_statuses()?.0;
 
         assert_that!(statuses).has_length(1);
-        repo.stash_failed_version(Tag::from_str("1.0.0", None)?)?;
+        repo.stash_failed_version(Tag::from_str("1.0.0", None, repo.settings())?)?;
 
         let statuses = repo.get_statuses()?.0;
         assert_that!(statuses).is_empty();
@@ -1,7 +1,7 @@
 use crate::conventional::version::Increment;
 use crate::git::error::{Git2Error, TagError};
 use crate::git::repository::Repository;
-use crate::SETTINGS;
+use crate::settings::Settings;
 use git2::Oid;
 use semver::Version;
 use std::cmp::Ordering;
@@ -22,7 +22,7 @@ impl Repository {
             .resolve_reference_from_short_name(tag)
             .map_err(|err| TagError::not_found(tag, err))
             .map(|reference| reference.target().unwrap())
-            .map(|oid| Tag::from_str(tag, Some(oid)))?
+            .map(|oid| Tag::from_str(tag, Some(oid), self.settings()))?
     }
 
     pub(crate) fn create_tag(&self, tag: &Tag) -> Result<(), Git2Error> {
@@ -62,13 +62,15 @@ impl Repository {
     }
 
     fn tags(&self) -> Result<Vec<String>, TagError> {
-        let packages: Vec<&str> = SETTINGS
+        let packages: Vec<&str> = self
+            .settings()
             .packages
             .keys()
             .map(|profile| -> &str { profile })
             .collect();
 
-        let pattern = SETTINGS
+        let pattern = self
+            .settings()
             .tag_prefix
             .as_ref()
             .map(|prefix| format!("{}*", prefix));
@@ -105,6 +107,8 @@ pub struct Tag {
     pub prefix: Option<String>,
     pub version: Version,
     pub oid: Option<Oid>,
+    /// The configured `monorepo_version_separator`, used to display package tags
+    pub(crate) separator: Option<String>,
 }
 
 impl Ord for Tag {
@@ -137,7 +141,13 @@ impl PartialOrd<Tag> for Tag {
 
 impl Default for Tag {
     fn default() -> Self {
-        Tag::create(Version::new(0, 0, 0), None)
+        Tag {
+            package: None,
+            prefix: None,
+            version: Version::new(0, 0, 0),
+            oid: None,
+            separator: None,
+        }
     }
 }
 
@@ -150,12 +160,13 @@ impl Tag {
         self.oid.as_ref().unwrap()
     }
 
-    pub(crate) fn create(version: Version, package: Option<String>) -> Self {
+    pub(crate) fn create(version: Version, package: Option<String>, settings: &Settings) -> Self {
         Tag {
             package,
-            prefix: SETTINGS.tag_prefix.clone(),
+            prefix: settings.tag_prefix.clone(),
             version,
             oid: None,
+            separator: settings.monorepo_separator().map(str::to_string),
         }
     }
 
@@ -163,29 +174,33 @@ impl Tag {
         self.oid.as_ref()
     }
 
-    pub(crate) fn from_str(raw: &str, oid: Option<Oid>) -> Result<Tag, TagError> {
-        let prefix = SETTINGS.tag_prefix.as_ref();
+    pub(crate) fn from_str(
+        raw: &str,
+        oid: Option<Oid>,
+        settings: &Settings,
+    ) -> Result<Tag, TagError> {
+        let prefix = settings.tag_prefix.as_ref();
+        let separator = settings.monorepo_separator();
 
-        let package_tag: Option<Tag> = SETTINGS
+        let package_tag: Option<Tag> = settings
             .packages
             .keys()
             .filter_map(|package_name| {
                 raw.strip_prefix(package_name)
-                    .zip(SETTINGS.monorepo_separator())
-                    .and_then(|(remains, prefix)| remains.strip_prefix(prefix))
+                    .zip(separator)
+                    .and_then(|(remains, separator)| remains.strip_prefix(separator))
                     .map(|remains| {
-                        SETTINGS
-                            .tag_prefix
-                            .as_ref()
+                        prefix
                             .and_then(|prefix| remains.strip_prefix(prefix))
                             .unwrap_or(remains)
                     })
                     .and_then(|version| Version::parse(version).ok())
                     .map(|version| Tag {
                         package: Some(package_name.to_string()),
-                        prefix: SETTINGS.tag_prefix.clone(),
+                        prefix: prefix.cloned(),
                         version,
                         oid,
+                        separator: separator.map(str::to_string),
                     })
             })
             .next();
@@ -204,6 +219,7 @@ impl Tag {
                 prefix: prefix.cloned(),
                 version,
                 oid,
+                separator: separator.map(str::to_string),
             })
         }
     }
@@ -229,12 +245,12 @@ impl fmt::Display for Tag {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let version = self.version.to_string();
         if let Some((package, prefix)) = self.package.as_ref().zip(self.prefix.as_ref()) {
-            let separator = SETTINGS.monorepo_separator().unwrap_or_else(||
+            let separator = self.separator.as_deref().unwrap_or_else(||
                 panic!("Found a tag with monorepo package prefix but 'monorepo_version_separator' is not defined")
             );
             write!(f, "{package}{separator}{prefix}{version}")
         } else if let Some(package) = self.package.as_ref() {
-            let separator = SETTINGS.monorepo_separator().unwrap_or_else(||
+            let separator = self.separator.as_deref().unwrap_or_else(||
                 panic!("Found a tag with monorepo package prefix but 'monorepo_version_separator' is not defined")
             );
 
@@ -258,92 +274,84 @@ mod test {
     use semver::Version;
     use speculoos::prelude::*;
     use std::collections::HashMap;
-    use std::fs;
     use std::path::PathBuf;
 
     #[test]
     fn should_compare_tags() -> Result<()> {
-        let v1_0_0 = Tag::from_str("1.0.0", None)?;
-        let v1_1_0 = Tag::from_str("1.1.0", None)?;
-        let v2_1_0 = Tag::from_str("2.1.0", None)?;
-        let v0_1_0 = Tag::from_str("0.1.0", None)?;
-        let v0_2_0 = Tag::from_str("0.2.0", None)?;
-        let v0_0_1 = Tag::from_str("0.0.1", None)?;
+        let settings = Settings::default();
+        let v1_0_0 = Tag::from_str("1.0.0", None, &settings)?;
+        let v1_1_0 = Tag::from_str("1.1.0", None, &settings)?;
+        let v2_1_0 = Tag::from_str("2.1.0", None, &settings)?;
+        let v0_1_0 = Tag::from_str("0.1.0", None, &settings)?;
+        let v0_2_0 = Tag::from_str("0.2.0", None, &settings)?;
+        let v0_0_1 = Tag::from_str("0.0.1", None, &settings)?;
         assert_that!([v1_0_0, v1_1_0, v2_1_0, v0_1_0, v0_2_0, v0_0_1,]
             .iter()
             .max())
         .is_some()
-        .is_equal_to(&Tag::from_str("2.1.0", None)?);
+        .is_equal_to(&Tag::from_str("2.1.0", None, &settings)?);
 
         Ok(())
     }
 
-    #[sealed_test]
+    #[test]
     fn should_compare_tags_with_prefix() -> Result<()> {
-        Repository::init(".")?;
         let settings = Settings {
             tag_prefix: Some("v".to_string()),
             ..Default::default()
         };
-        let settings = toml::to_string(&settings)?;
-        fs::write("cog.toml", settings)?;
-
-        let v1_0_0 = Tag::from_str("v1.0.0", None)?;
-        let v1_1_0 = Tag::from_str("v1.1.0", None)?;
-        let v2_1_0 = Tag::from_str("v2.1.0", None)?;
-        let v0_1_0 = Tag::from_str("v0.1.0", None)?;
-        let v0_2_0 = Tag::from_str("v0.2.0", None)?;
-        let v0_0_1 = Tag::from_str("v0.0.1", None)?;
+
+        let v1_0_0 = Tag::from_str("v1.0.0", None, &settings)?;
+        let v1_1_0 = Tag::from_str("v1.1.0", None, &settings)?;
+        let v2_1_0 = Tag::from_str("v2.1.0", None, &settings)?;
+        let v0_1_0 = Tag::from_str("v0.1.0", None, &settings)?;
+        let v0_2_0 = Tag::from_str("v0.2.0", None, &settings)?;
+        let v0_0_1 = Tag::from_str("v0.0.1", None, &settings)?;
         assert_that!([v1_0_0, v1_1_0, v2_1_0, v0_1_0, v0_2_0, v0_0_1,]
             .iter()
             .max())
         .is_some()
-        .is_equal_to(&Tag::from_str("2.1.0", None)?);
+        .is_equal_to(&Tag::from_str("2.1.0", None, &settings)?);
 
         Ok(())
     }
 
     #[test]
     fn should_get_tag_from_str() -> Result<()> {
-        let tag = Tag::from_str("1.0.0", None);
+        let tag = Tag::from_str("1.0.0", None, &Settings::default());
         assert_that!(tag).is_ok().is_equal_to(Tag {
             package: None,
             prefix: None,
             version: Version::new(1, 0, 0),
             oid: None,
+            ..Default::default()
         });
 
         Ok(())
     }
 
-    #[sealed_test]
+    #[test]
     fn should_get_tag_from_str_with_prefix() -> Result<()> {
-        Repository::init(".")?;
-
         let settings = Settings {
             tag_prefix: Some("v".to_string()),
             ..Default::default()
         };
 
-        let settings = toml::to_string(&settings)?;
-        fs::write("cog.toml", settings)?;
-
-        let tag = Tag::from_str("v1.0.0", None);
+        let tag = Tag::from_str("v1.0.0", None, &settings);
 
         assert_that!(tag).is_ok().is_equal_to(Tag {
             package: None,
             prefix: Some("v".to_string()),
             version: Version::new(1, 0, 0),
             oid: None,
+            ..Default::default()
         });
 
         Ok(())
     }
 
-    #[sealed_test]
+    #[test]
     fn should_get_tag_from_str_with_separator() -> Result<()> {
-        Repository::init(".")?;
-
         let mut packages = HashMap::new();
         packages.insert("one".to_string(), Default::default());
         let settings = Settings {
@@ -351,25 +359,21 @@ mod test {
             ..Default::default()
         };
 
-        let settings = toml::to_string(&settings)?;
-        fs::write("cog.toml", settings)?;
-
-        let tag = Tag::from_str("one-1.0.0", None);
+        let tag = Tag::from_str("one-1.0.0", None, &settings);
 
         assert_that!(tag).is_ok().is_equal_to(Tag {
             package: Some("one".to_string()),
             prefix: None,
             version: Version::new(1, 0, 0),
             oid: None,
+            ..Default::default()
         });
 
         Ok(())
     }
 
-    #[sealed_test]
+    #[test]
     fn should_get_tag_from_str_with_prefix_and_separator() -> Result<()> {
-        Repository::init(".")?;
-
         let mut packages = HashMap::new();
         packages.insert("one".to_string(), Default::default());
         let settings = Settings {
@@ -378,25 +382,21 @@ mod test {
             ..Default::default()
         };
 
-        let settings = toml::to_string(&settings)?;
-        fs::write("cog.toml", settings)?;
-
-        let tag = Tag::from_str("one-v1.0.0", None);
+        let tag = Tag::from_str("one-v1.0.0", None, &settings);
 
         assert_that!(tag).is_ok().is_equal_to(Tag {
             package: Some("one".to_string()),
             prefix: Some("v".to_string()),
             version: Version::new(1, 0, 0),
             oid: None,
+            ..Default::default()
         });
 
         Ok(())
     }
 
-    #[sealed_test]
+    #[test]
     fn should_get_tag_from_str_with_prefix_and_custom_separator() -> Result<()> {
-        Repository::init(".")?;
-
         let mut packages = HashMap::new();
         packages.insert("one".to_string(), Default::default());
         let settings = Settings {
@@ -406,16 +406,14 @@ mod test {
             ..Default::default()
         };
 
-        let settings = toml::to_string(&settings)?;
-        fs::write("cog.toml", settings)?;
-
-        let tag = Tag::from_str("one#v1.0.0", None);
+        let tag = Tag::from_str("one#v1.0.0", None, &settings);
 
         assert_that!(tag).is_ok().is_equal_to(Tag {
             package: Some("one".to_string()),
             prefix: Some("v".to_string()),
             version: Version::new(1, 0, 0),
             oid: None,
+            ..Default::default()
         });
 
         Ok(())
@@ -541,13 +539,10 @@ mod test {
             ..Default::default()
         };
 
-        let repo = Repository::init(".")?;
-        let settings = toml::to_string(&settings)?;
+        let repo = Repository::init(".")?.with_settings(settings);
 
         run_cmd!(
-            echo $settings > cog.toml;
-            git add .;
-            git commit -m "first commit";
+            git commit --allow-empty -m "first commit";
             git commit --allow-empty -m "feature one";
             git tag lunatic-timer-api-v0.12.0;
         )?;
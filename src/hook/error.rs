@@ -8,6 +8,18 @@ pub struct HookParseError {
     error: Box<dyn Error + Sync + Send>,
 }
 
+impl HookParseError {
+    pub(crate) fn unknown_variable(variable: &str) -> Self {
+        Self {
+            error: format!(
+                "unknown variable `{variable}`, expected one of `version`, `latest`, `package`, \
+                `packages`, `package_path`, `tag`, `version_tag`, `changelog_path`, `branch` or `commit_sha`"
+            )
+            .into(),
+        }
+    }
+}
+
 impl From<semver::Error> for HookParseError {
     fn from(err: semver::Error) -> Self {
         Self {
@@ -1,16 +1,25 @@
 mod error;
 mod parser;
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::io;
 use std::ops::Range;
-use std::process::Command;
+use std::path::PathBuf;
+use std::process::{Child, Command};
 use std::str::FromStr;
-use std::{fmt, path};
+use std::time::{Duration, Instant};
+use std::{fmt, path, thread};
 
+use crate::conventional::version::Increment;
+use crate::settings::{HookCondition, HookConfig};
 use crate::Tag;
-use parser::Token;
+use parser::{Token, VersionField};
 
-use anyhow::{anyhow, ensure, Result};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use globset::Glob;
+use itertools::Itertools;
+use log::warn;
+use serde::Serialize;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct VersionSpan {
@@ -28,21 +37,129 @@ impl HookVersion {
     }
 }
 
+/// Values that can be inserted in hooks alongside versions, ex: `{{package}}` or `{{branch}}`.
+#[derive(Debug, Default)]
+pub(crate) struct HookContext {
+    pub package: Option<String>,
+    pub package_path: Option<PathBuf>,
+    pub changelog_path: Option<PathBuf>,
+    pub branch: Option<String>,
+    pub commit_sha: Option<String>,
+    pub changelog: Option<String>,
+    pub dry_run: bool,
+    /// Packages bumped along with a global monorepo version
+    pub packages: Option<Vec<BumpedPackage>>,
+}
+
+/// A package bumped during a monorepo bump, exposed to global hooks.
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub(crate) struct BumpedPackage {
+    pub name: String,
+    pub path: PathBuf,
+    pub previous_version: Option<String>,
+    pub version: String,
+    pub tag: String,
+}
+
+impl HookContext {
+    /// Environment variables exported to every hook process.
+    pub(crate) fn env(
+        &self,
+        current: Option<&HookVersion>,
+        next: &HookVersion,
+    ) -> Vec<(&'static str, String)> {
+        let previous = current.map(|version| &version.prefixed_tag);
+        let increment = get_increment(current, next)
+            .map(|increment| match increment {
+                Increment::Major => "major",
+                Increment::Minor => "minor",
+                Increment::Patch => "patch",
+            })
+            .unwrap_or("none");
+
+        vec![
+            ("COG_VERSION", next.prefixed_tag.version.to_string()),
+            (
+                "COG_PREVIOUS_VERSION",
+                previous
+                    .map(|tag| tag.version.to_string())
+                    .unwrap_or_default(),
+            ),
+            ("COG_TAG", next.prefixed_tag.to_string()),
+            ("COG_PACKAGE", self.package.clone().unwrap_or_default()),
+            ("COG_INCREMENT", increment.to_string()),
+            ("COG_CHANGELOG", self.changelog.clone().unwrap_or_default()),
+            ("COG_DRY_RUN", self.dry_run.to_string()),
+        ]
+    }
+}
+
+fn get_increment(current: Option<&HookVersion>, next: &HookVersion) -> Option<Increment> {
+    let current = current.map(|version| &version.prefixed_tag);
+    next.prefixed_tag
+        .get_increment_from(current.unwrap_or(&Tag::default()))
+}
+
 impl VersionSpan {
-    pub(crate) fn build_version_str(
+    pub(crate) fn build_str(
         &mut self,
         version: &HookVersion,
         latest: Option<&HookVersion>,
+        context: &HookContext,
     ) -> Result<String> {
-        let version = version.prefixed_tag.version.clone();
+        let tag = &version.prefixed_tag;
         let latest = latest.map(|version| version.prefixed_tag.version.clone());
 
-        // According to the pest grammar, a `version` or `latest_version` token is expected first
+        // According to the pest grammar, a variable, `version` or `latest_version` token is expected first
         let mut version = match self.tokens.pop_front() {
-            Some(Token::Version) => Ok(version),
+            Some(Token::Version) => Ok(tag.version.clone()),
             Some(Token::LatestVersion) => {
                 latest.ok_or_else(|| anyhow!("No previous tag found to replace {{latest}} version"))
             }
+            Some(Token::Tag) => return Ok(tag.to_string()),
+            Some(Token::VersionTag) => {
+                let prefix = tag.prefix.as_deref().unwrap_or_default();
+                return Ok(format!("{prefix}{}", tag.version));
+            }
+            Some(Token::Packages) => {
+                return context
+                    .packages
+                    .as_ref()
+                    .map(|packages| packages.iter().map(|package| &package.name).join(" "))
+                    .ok_or_else(|| anyhow!("No monorepo bump to replace {{packages}}"))
+            }
+            Some(Token::Package) => {
+                return context
+                    .package
+                    .clone()
+                    .ok_or_else(|| anyhow!("No package to replace {{package}}"))
+            }
+            Some(Token::PackagePath) => {
+                return context
+                    .package_path
+                    .as_ref()
+                    .map(|path| path.display().to_string())
+                    .ok_or_else(|| anyhow!("No package to replace {{package_path}}"))
+            }
+            Some(Token::ChangelogPath) => {
+                return context
+                    .changelog_path
+                    .as_ref()
+                    .map(|path| path.display().to_string())
+                    .ok_or_else(|| anyhow!("No changelog path to replace {{changelog_path}}"))
+            }
+            Some(Token::Branch) => {
+                return context
+                    .branch
+                    .clone()
+                    .ok_or_else(|| anyhow!("No branch found to replace {{branch}}"))
+            }
+            Some(Token::CommitSha) => {
+                return context
+                    .commit_sha
+                    .clone()
+                    .ok_or_else(|| anyhow!("No commit found to replace {{commit_sha}}"))
+            }
             _ => unreachable!("Unexpected parsing error"),
         }?;
 
@@ -68,6 +185,16 @@ impl VersionSpan {
                 // set  build metadata and prerelease
                 Token::PreRelease(pre_release) => version.pre = pre_release,
                 Token::BuildMetadata(build) => version.build = build,
+                // a version accessor is always the last token
+                Token::Accessor(field) => {
+                    return Ok(match field {
+                        VersionField::Major => version.major.to_string(),
+                        VersionField::Minor => version.minor.to_string(),
+                        VersionField::Patch => version.patch.to_string(),
+                        VersionField::PreRelease => version.pre.to_string(),
+                        VersionField::BuildMetadata => version.build.to_string(),
+                    })
+                }
                 _ => unreachable!("Unexpected parsing error"),
             }
         }
@@ -87,13 +214,13 @@ impl HookSpan {
         &mut self,
         version: &HookVersion,
         latest: Option<&HookVersion>,
+        context: &HookContext,
     ) -> Result<String> {
         let mut output = self.content.clone();
-        if let Some(mut span) = self.version_spans.pop() {
-            let version_str = span.build_version_str(version, latest)?;
-            let version_str = version_str.as_str();
-            output.replace_range(span.range.clone(), version_str);
-            output = parser::parse(&output)?.replace_versions(version, latest)?;
+        // Replace spans from the end so the remaining ranges stay valid
+        while let Some(mut span) = self.version_spans.pop() {
+            let replacement = span.build_str(version, latest, context)?;
+            output.replace_range(span.range.clone(), &replacement);
         }
 
         Ok(output)
@@ -101,20 +228,65 @@ impl HookSpan {
 }
 
 #[derive(Debug)]
-pub struct Hook(String);
+pub struct Hook {
+    command: String,
+    options: HookOptions,
+}
+
+/// Execution options of a hook, see [`crate::settings::HookEntry`].
+#[derive(Debug, Default)]
+struct HookOptions {
+    shell: Option<String>,
+    cwd: Option<PathBuf>,
+    timeout: Option<Duration>,
+    retries: u32,
+    allow_failure: bool,
+    env: HashMap<String, String>,
+    when: Option<HookCondition>,
+    when_branch: Option<String>,
+    parallel: Option<String>,
+}
 
 impl FromStr for Hook {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         ensure!(!s.is_empty(), "hook must not be an empty string");
-        Ok(Hook(s.to_string()))
+        Ok(Hook {
+            command: s.to_string(),
+            options: HookOptions::default(),
+        })
+    }
+}
+
+impl TryFrom<&HookConfig> for Hook {
+    type Error = anyhow::Error;
+
+    fn try_from(config: &HookConfig) -> Result<Self, Self::Error> {
+        match config {
+            HookConfig::Command(command) => command.parse(),
+            HookConfig::Detailed(entry) => {
+                let mut hook: Hook = entry.command.parse()?;
+                hook.options = HookOptions {
+                    shell: entry.shell.clone(),
+                    cwd: entry.cwd.clone(),
+                    timeout: entry.timeout.map(Duration::from_secs),
+                    retries: entry.retries,
+                    allow_failure: entry.allow_failure,
+                    env: entry.env.clone(),
+                    when: entry.when,
+                    when_branch: entry.when_branch.clone(),
+                    parallel: entry.parallel.clone(),
+                };
+                Ok(hook)
+            }
+        }
     }
 }
 
 impl fmt::Display for Hook {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(&self.0)
+        f.write_str(&self.command)
     }
 }
 
@@ -123,33 +295,185 @@ impl Hook {
         &mut self,
         current_version: Option<&HookVersion>,
         next_version: &HookVersion,
+        context: &HookContext,
     ) -> Result<()> {
-        let mut parts = parser::parse(&self.0)?;
-        self.0 = parts.replace_versions(next_version, current_version)?;
+        let mut parts = parser::parse(&self.command)?;
+        self.command = parts.replace_versions(next_version, current_version, context)?;
 
         Ok(())
     }
 
-    pub fn run(&self, package_path: Option<&path::Path>) -> Result<()> {
-        let mut cmd = Command::new("sh");
-        let cmd = cmd.arg("-c").arg(&self.0);
-        if let Some(current_dir) = package_path {
+    /// Check the hook version DSL and `when_branch` pattern without running anything.
+    pub(crate) fn validate(&self) -> Result<()> {
+        parser::parse(&self.command)?;
+
+        if let Some(pattern) = &self.options.when_branch {
+            Glob::new(pattern)
+                .with_context(|| format!("invalid when_branch pattern '{pattern}'"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate the `when` and `when_branch` conditions of this hook.
+    pub(crate) fn should_run(
+        &self,
+        current_version: Option<&HookVersion>,
+        next_version: &HookVersion,
+        context: &HookContext,
+    ) -> Result<bool> {
+        let matches_condition = match self.options.when {
+            None => true,
+            Some(HookCondition::Prerelease) => !next_version.prefixed_tag.version.pre.is_empty(),
+            Some(condition) => {
+                let increment = get_increment(current_version, next_version);
+                matches!(
+                    (condition, increment),
+                    (HookCondition::Major, Some(Increment::Major))
+                        | (HookCondition::Minor, Some(Increment::Minor))
+                        | (HookCondition::Patch, Some(Increment::Patch))
+                )
+            }
+        };
+
+        let matches_branch = match &self.options.when_branch {
+            None => true,
+            Some(pattern) => {
+                let glob = Glob::new(pattern)
+                    .with_context(|| format!("invalid when_branch pattern '{pattern}'"))?
+                    .compile_matcher();
+                context
+                    .branch
+                    .as_ref()
+                    .is_some_and(|branch| glob.is_match(branch))
+            }
+        };
+
+        Ok(matches_condition && matches_branch)
+    }
+
+    pub(crate) fn parallel_group(&self) -> Option<&str> {
+        self.options.parallel.as_deref()
+    }
+
+    /// Run the hook from `base_dir`, the process working directory if `None`.
+    /// The `cwd` option of the hook is relative to it.
+    pub fn run(&self, base_dir: Option<&path::Path>, env: &[(&str, String)]) -> Result<()> {
+        let attempts = self.options.retries + 1;
+        let mut outcome = Ok(());
+
+        for attempt in 1..=attempts {
+            outcome = self.run_once(base_dir, env);
+            match &outcome {
+                Ok(()) => return Ok(()),
+                Err(err) if attempt < attempts => {
+                    warn!("{err}, retrying ({attempt}/{})", self.options.retries)
+                }
+                Err(_) => {}
+            }
+        }
+
+        match outcome {
+            Err(err) if self.options.allow_failure => {
+                warn!("{err}, failure allowed, continuing");
+                Ok(())
+            }
+            outcome => outcome,
+        }
+    }
+
+    fn run_once(&self, base_dir: Option<&path::Path>, env: &[(&str, String)]) -> Result<()> {
+        let mut cmd = self.command()?;
+        cmd.envs(env.iter().map(|(key, value)| (key, value)));
+        cmd.envs(&self.options.env);
+
+        let current_dir = match (base_dir, &self.options.cwd) {
+            (Some(base_dir), Some(cwd)) => Some(base_dir.join(cwd)),
+            (None, Some(cwd)) => Some(cwd.clone()),
+            (base_dir, None) => base_dir.map(path::Path::to_path_buf),
+        };
+
+        if let Some(current_dir) = current_dir {
             cmd.current_dir(current_dir);
         }
-        let status = cmd.status()?;
+
+        let status = match self.options.timeout {
+            None => cmd.status()?,
+            Some(timeout) => {
+                // Run the hook in its own process group, so the processes it spawns
+                // can be killed along with it on timeout
+                #[cfg(unix)]
+                std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+
+                let mut child = cmd.spawn()?;
+                let start = Instant::now();
+                loop {
+                    if let Some(status) = child.try_wait()? {
+                        break status;
+                    }
+
+                    if start.elapsed() >= timeout {
+                        kill_process_group(&mut child)?;
+                        child.wait()?;
+                        bail!("hook timed out after {}s", timeout.as_secs());
+                    }
+
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+        };
+
         ensure!(status.success(), "hook failed with status {}", status);
         Ok(())
     }
+
+    // A single word shell is invoked with `-c`, otherwise the command is appended to the shell arguments
+    fn command(&self) -> Result<Command> {
+        let shell = self.options.shell.as_deref().unwrap_or("sh");
+        let mut args = shell_words::split(shell)?.into_iter();
+        let program = args
+            .next()
+            .ok_or_else(|| anyhow!("hook shell must not be empty"))?;
+        let mut args: Vec<String> = args.collect();
+        if args.is_empty() {
+            args.push("-c".to_string());
+        }
+
+        let mut cmd = Command::new(program);
+        cmd.args(args).arg(&self.command);
+        Ok(cmd)
+    }
+}
+
+/// Kill the hook process along with the processes it spawned,
+/// the hook being the leader of its own process group.
+#[cfg(unix)]
+fn kill_process_group(child: &mut Child) -> io::Result<()> {
+    let pgid = -(child.id() as libc::pid_t);
+    // SAFETY: `kill` has no memory safety requirement, a negative pid targets the process group
+    match unsafe { libc::kill(pgid, libc::SIGKILL) } {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(child: &mut Child) -> io::Result<()> {
+    child.kill()
 }
 
 #[cfg(test)]
 mod test {
     use git2::Repository;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::PathBuf;
     use std::str::FromStr;
 
     use crate::{Result, Tag};
 
-    use crate::hook::{Hook, HookVersion};
+    use crate::hook::{BumpedPackage, Hook, HookContext, HookVersion};
+    use crate::settings::{HookCondition, HookConfig, HookEntry, Settings};
     use sealed_test::prelude::*;
     use speculoos::prelude::*;
 
@@ -162,57 +486,78 @@ mod test {
     #[test]
     fn parse_valid_string() -> Result<()> {
         let hook = Hook::from_str("cargo bump {{version}}")?;
-        assert_that!(hook.0.as_str()).is_equal_to("cargo bump {{version}}");
+        assert_that!(hook.command.as_str()).is_equal_to("cargo bump {{version}}");
         Ok(())
     }
 
     #[test]
     fn replace_version_cargo() -> Result<()> {
         let mut hook = Hook::from_str("cargo bump {{version}}")?;
-        hook.insert_versions(None, &HookVersion::new(Tag::from_str("1.0.0", None)?))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
+        )
+        .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("cargo bump 1.0.0");
+        assert_that!(hook.command.as_str()).is_equal_to("cargo bump 1.0.0");
         Ok(())
     }
 
     #[test]
     fn replace_maven_version() -> Result<()> {
         let mut hook = Hook::from_str("mvn versions:set -DnewVersion={{version}}")?;
-        hook.insert_versions(None, &HookVersion::new(Tag::from_str("1.0.0", None)?))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
+        )
+        .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("mvn versions:set -DnewVersion=1.0.0");
+        assert_that!(hook.command.as_str()).is_equal_to("mvn versions:set -DnewVersion=1.0.0");
         Ok(())
     }
 
     #[test]
     fn replace_maven_version_with_expression() -> Result<()> {
         let mut hook = Hook::from_str("mvn versions:set -DnewVersion={{version+1minor-SNAPSHOT}}")?;
-        hook.insert_versions(None, &HookVersion::new(Tag::from_str("1.0.0", None)?))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
+        )
+        .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("mvn versions:set -DnewVersion=1.1.0-SNAPSHOT");
+        assert_that!(hook.command.as_str())
+            .is_equal_to("mvn versions:set -DnewVersion=1.1.0-SNAPSHOT");
         Ok(())
     }
 
     #[test]
     fn leave_hook_untouched_when_no_version() -> Result<()> {
         let mut hook = Hook::from_str("echo \"Hello World\"")?;
-        hook.insert_versions(None, &HookVersion::new(Tag::from_str("1.0.0", None)?))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
+        )
+        .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("echo \"Hello World\"");
+        assert_that!(hook.command.as_str()).is_equal_to("echo \"Hello World\"");
         Ok(())
     }
 
     #[test]
     fn replace_quoted_version() -> Result<()> {
         let mut hook = Hook::from_str("echo \"{{version}}\"")?;
-        hook.insert_versions(None, &HookVersion::new(Tag::from_str("1.0.0", None)?))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
+        )
+        .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("echo \"1.0.0\"");
+        assert_that!(hook.command.as_str()).is_equal_to("echo \"1.0.0\"");
         Ok(())
     }
 
@@ -220,10 +565,15 @@ mod test {
     fn replace_version_with_nested_simple_quoted_arg() -> Result<()> {
         let mut hook =
             Hook::from_str("cog commit chore 'bump snapshot to {{version+1minor-pre}}'")?;
-        hook.insert_versions(None, &HookVersion::new(Tag::from_str("1.0.0", None)?))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
+        )
+        .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("cog commit chore 'bump snapshot to 1.1.0-pre'");
+        assert_that!(hook.command.as_str())
+            .is_equal_to("cog commit chore 'bump snapshot to 1.1.0-pre'");
         Ok(())
     }
 
@@ -231,10 +581,14 @@ mod test {
     fn replace_version_with_nested_double_quoted_arg() -> Result<()> {
         let mut hook =
             Hook::from_str("cog commit chore \"bump snapshot to {{version+1minor-pre}}\"")?;
-        hook.insert_versions(None, &HookVersion::new(Tag::from_str("1.0.0", None)?))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
+        )
+        .unwrap();
 
-        assert_that!(hook.0.as_str())
+        assert_that!(hook.command.as_str())
             .is_equal_to("cog commit chore \"bump snapshot to 1.1.0-pre\"");
         Ok(())
     }
@@ -243,12 +597,18 @@ mod test {
     fn replace_version_with_multiple_placeholders() -> Result<()> {
         let mut hook = Hook::from_str("echo \"the latest {{latest}}, the greatest {{version}}\"")?;
         hook.insert_versions(
-            Some(&HookVersion::new(Tag::from_str("0.5.9", None)?)),
-            &HookVersion::new(Tag::from_str("1.0.0", None)?),
+            Some(&HookVersion::new(Tag::from_str(
+                "0.5.9",
+                None,
+                &Settings::default(),
+            )?)),
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
         )
         .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("echo \"the latest 0.5.9, the greatest 1.0.0\"");
+        assert_that!(hook.command.as_str())
+            .is_equal_to("echo \"the latest 0.5.9, the greatest 1.0.0\"");
         Ok(())
     }
 
@@ -258,12 +618,18 @@ mod test {
             "echo \"the latest {{latest+3major+1minor}}, the greatest {{version+2patch}}\"",
         )?;
         hook.insert_versions(
-            Some(&HookVersion::new(Tag::from_str("0.5.9", None)?)),
-            &HookVersion::new(Tag::from_str("1.0.0", None)?),
+            Some(&HookVersion::new(Tag::from_str(
+                "0.5.9",
+                None,
+                &Settings::default(),
+            )?)),
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
         )
         .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("echo \"the latest 3.1.0, the greatest 1.0.2\"");
+        assert_that!(hook.command.as_str())
+            .is_equal_to("echo \"the latest 3.1.0, the greatest 1.0.2\"");
         Ok(())
     }
 
@@ -271,24 +637,322 @@ mod test {
     fn replace_version_with_pre_and_build_metadata() -> Result<()> {
         let mut hook =
             Hook::from_str("echo \"the latest {{version+1major-pre.alpha-bravo+build.42}}\"")?;
-        hook.insert_versions(None, &HookVersion::new(Tag::from_str("1.0.0", None)?))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
+        )
+        .unwrap();
 
-        assert_that!(hook.0.as_str())
+        assert_that!(hook.command.as_str())
             .is_equal_to("echo \"the latest 2.0.0-pre.alpha-bravo+build.42\"");
         Ok(())
     }
 
+    #[test]
+    fn replace_version_accessors() -> Result<()> {
+        let mut hook = Hook::from_str(
+            "echo {{version.major}} {{version.minor}} {{version+1patch.patch}} {{latest.major}}",
+        )?;
+        hook.insert_versions(
+            Some(&HookVersion::new(Tag::from_str(
+                "0.5.9",
+                None,
+                &Settings::default(),
+            )?)),
+            &HookVersion::new(Tag::from_str("1.2.3", None, &Settings::default())?),
+            &HookContext::default(),
+        )
+        .unwrap();
+
+        assert_that!(hook.command.as_str()).is_equal_to("echo 1 2 4 0");
+        Ok(())
+    }
+
+    #[test]
+    fn replace_context_variables() -> Result<()> {
+        let mut hook = Hook::from_str(
+            "echo {{package}} {{package_path}} {{changelog_path}} {{branch}} {{commit_sha}} {{tag}} {{version_tag}}",
+        )?;
+        let context = HookContext {
+            package: Some("one".to_string()),
+            package_path: Some(PathBuf::from("packages/one")),
+            changelog_path: Some(PathBuf::from("packages/one/CHANGELOG.md")),
+            branch: Some("main".to_string()),
+            commit_sha: Some("6ba7e7e".to_string()),
+            ..Default::default()
+        };
+
+        hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &context,
+        )
+        .unwrap();
+
+        assert_that!(hook.command.as_str()).is_equal_to(
+            "echo one packages/one packages/one/CHANGELOG.md main 6ba7e7e 1.0.0 1.0.0",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn replace_packages_variable() -> Result<()> {
+        let mut hook = Hook::from_str("./publish.sh {{packages}}")?;
+        let package = |name: &str| BumpedPackage {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+            previous_version: None,
+            version: "0.1.0".to_string(),
+            tag: format!("{name}-0.1.0"),
+        };
+        let context = HookContext {
+            packages: Some(vec![package("one"), package("two")]),
+            ..Default::default()
+        };
+
+        hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &context,
+        )
+        .unwrap();
+
+        assert_that!(hook.command.as_str()).is_equal_to("./publish.sh one two");
+        Ok(())
+    }
+
+    #[test]
+    fn missing_package_variable_is_err() -> Result<()> {
+        let mut hook = Hook::from_str("echo {{package}}")?;
+
+        let result = hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
+        );
+
+        assert_that!(result).is_err();
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_variable_is_err() -> Result<()> {
+        let mut hook = Hook::from_str("echo {{pakage}}")?;
+
+        let result = hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
+        );
+
+        assert_that!(result).is_err();
+        Ok(())
+    }
+
+    #[test]
+    fn other_template_syntax_is_kept() -> Result<()> {
+        let mut hook = Hook::from_str("docker inspect --format '{{.Id}}' cog:{{version}}")?;
+
+        hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
+        )?;
+
+        assert_that!(hook.command.as_str())
+            .is_equal_to("docker inspect --format '{{.Id}}' cog:1.0.0");
+        Ok(())
+    }
+
+    #[test]
+    fn build_hook_env() -> Result<()> {
+        let context = HookContext {
+            package: Some("one".to_string()),
+            changelog: Some("## 1.1.0".to_string()),
+            ..Default::default()
+        };
+
+        let env = context.env(
+            Some(&HookVersion::new(Tag::from_str(
+                "1.0.0",
+                None,
+                &Settings::default(),
+            )?)),
+            &HookVersion::new(Tag::from_str("1.1.0", None, &Settings::default())?),
+        );
+
+        assert_that!(env).contains_all_of(&[
+            &("COG_VERSION", "1.1.0".to_string()),
+            &("COG_PREVIOUS_VERSION", "1.0.0".to_string()),
+            &("COG_TAG", "1.1.0".to_string()),
+            &("COG_PACKAGE", "one".to_string()),
+            &("COG_INCREMENT", "minor".to_string()),
+            &("COG_CHANGELOG", "## 1.1.0".to_string()),
+            &("COG_DRY_RUN", "false".to_string()),
+        ]);
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn hook_env_is_exported() -> Result<()> {
+        Repository::init(".")?;
+        let hook = Hook::from_str("test \"$COG_VERSION\" = \"1.0.0\"")?;
+
+        let outcome = hook.run(None, &[("COG_VERSION", "1.0.0".to_string())]);
+
+        assert_that!(outcome).is_ok();
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn hook_entry_runs_with_shell_cwd_and_env() -> Result<()> {
+        fs::create_dir("sub")?;
+        let hook = Hook::try_from(&HookConfig::Detailed(HookEntry {
+            command: "[[ \"$NAME\" == cog ]] && touch out".to_string(),
+            shell: Some("bash".to_string()),
+            cwd: Some(PathBuf::from("sub")),
+            env: HashMap::from([("NAME".to_string(), "cog".to_string())]),
+            ..Default::default()
+        }))?;
+
+        let outcome = hook.run(None, &[]);
+
+        assert_that!(outcome).is_ok();
+        assert_that!(PathBuf::from("sub/out")).exists();
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn hook_entry_retries_until_success() -> Result<()> {
+        let hook = Hook::try_from(&HookConfig::Detailed(HookEntry {
+            command: "echo attempt >> attempts && [ $(wc -l < attempts) -eq 3 ]".to_string(),
+            retries: 2,
+            ..Default::default()
+        }))?;
+
+        let outcome = hook.run(None, &[]);
+
+        assert_that!(outcome).is_ok();
+        assert_that!(fs::read_to_string("attempts")?.lines().count()).is_equal_to(3);
+        Ok(())
+    }
+
+    #[test]
+    fn hook_entry_allowed_to_fail() -> Result<()> {
+        let hook = Hook::try_from(&HookConfig::Detailed(HookEntry {
+            command: "exit 1".to_string(),
+            allow_failure: true,
+            ..Default::default()
+        }))?;
+
+        let outcome = hook.run(None, &[]);
+
+        assert_that!(outcome).is_ok();
+        Ok(())
+    }
+
+    #[test]
+    fn hook_entry_times_out() -> Result<()> {
+        let hook = Hook::try_from(&HookConfig::Detailed(HookEntry {
+            command: "sleep 5".to_string(),
+            timeout: Some(1),
+            ..Default::default()
+        }))?;
+
+        let outcome = hook.run(None, &[]);
+
+        assert_that!(outcome)
+            .is_err()
+            .matches(|err| err.to_string() == "hook timed out after 1s");
+        Ok(())
+    }
+
+    #[sealed_test]
+    #[cfg(unix)]
+    fn hook_timeout_kills_spawned_processes() -> Result<()> {
+        let hook = Hook::try_from(&HookConfig::Detailed(HookEntry {
+            command: "(sleep 2 && touch orphan) & wait".to_string(),
+            timeout: Some(1),
+            ..Default::default()
+        }))?;
+
+        let outcome = hook.run(None, &[]);
+        std::thread::sleep(std::time::Duration::from_secs(2));
+
+        assert_that!(outcome).is_err();
+        assert_that!(PathBuf::from("orphan")).does_not_exist();
+        Ok(())
+    }
+
+    #[test]
+    fn hook_condition_on_increment() -> Result<()> {
+        let hook = Hook::try_from(&HookConfig::Detailed(HookEntry {
+            command: "echo major".to_string(),
+            when: Some(HookCondition::Major),
+            ..Default::default()
+        }))?;
+        let current = HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?);
+        let context = HookContext::default();
+
+        let major = hook.should_run(
+            Some(&current),
+            &HookVersion::new(Tag::from_str("2.0.0", None, &Settings::default())?),
+            &context,
+        )?;
+        let minor = hook.should_run(
+            Some(&current),
+            &HookVersion::new(Tag::from_str("1.1.0", None, &Settings::default())?),
+            &context,
+        )?;
+
+        assert_that!(major).is_true();
+        assert_that!(minor).is_false();
+        Ok(())
+    }
+
+    #[test]
+    fn hook_condition_on_prerelease_and_branch() -> Result<()> {
+        let hook = Hook::try_from(&HookConfig::Detailed(HookEntry {
+            command: "echo pre".to_string(),
+            when: Some(HookCondition::Prerelease),
+            when_branch: Some("release/*".to_string()),
+            ..Default::default()
+        }))?;
+        let next = HookVersion::new(Tag::from_str("1.1.0-rc.1", None, &Settings::default())?);
+        let on_branch = |branch: &str| HookContext {
+            branch: Some(branch.to_string()),
+            ..Default::default()
+        };
+
+        let on_release = hook.should_run(None, &next, &on_branch("release/1.1"))?;
+        let on_main = hook.should_run(None, &next, &on_branch("main"))?;
+        let not_prerelease = hook.should_run(
+            None,
+            &HookVersion::new(Tag::from_str("1.1.0", None, &Settings::default())?),
+            &on_branch("release/1.1"),
+        )?;
+
+        assert_that!(on_release).is_true();
+        assert_that!(on_main).is_false();
+        assert_that!(not_prerelease).is_false();
+        Ok(())
+    }
+
     #[sealed_test]
     fn parenthesis_in_hook_works() -> Result<()> {
         Repository::init(".")?;
 
         let mut hook = Hook::from_str("git commit --allow-empty -m 'chore(snapshot): bump snapshot to {{version+1patch-SNAPSHOT}}'")?;
 
-        hook.insert_versions(None, &HookVersion::new(Tag::from_str("1.0.0", None)?))
-            .unwrap();
+        hook.insert_versions(
+            None,
+            &HookVersion::new(Tag::from_str("1.0.0", None, &Settings::default())?),
+            &HookContext::default(),
+        )
+        .unwrap();
 
-        let outcome = hook.run(None);
+        let outcome = hook.run(None, &[]);
 
         assert_that!(outcome).is_ok();
 
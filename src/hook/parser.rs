@@ -24,6 +24,25 @@ pub enum Token {
     Patch,
     PreRelease(semver::Prerelease),
     BuildMetadata(semver::BuildMetadata),
+    Accessor(VersionField),
+    Package,
+    Packages,
+    PackagePath,
+    Tag,
+    VersionTag,
+    ChangelogPath,
+    Branch,
+    CommitSha,
+}
+
+/// A single field of a version, accessed with `{{version.<field>}}`
+#[derive(Debug, Eq, PartialEq)]
+pub enum VersionField {
+    Major,
+    Minor,
+    Patch,
+    PreRelease,
+    BuildMetadata,
 }
 
 pub fn parse(hook: &str) -> Result<HookSpan, HookParseError> {
@@ -37,9 +56,14 @@ pub fn parse(hook: &str) -> Result<HookSpan, HookParseError> {
     };
 
     for pair in pairs.into_inner() {
-        if pair.as_rule() == Rule::version {
-            let version_span = parse_version(pair)?;
-            span.version_spans.push(version_span);
+        match pair.as_rule() {
+            Rule::version => {
+                let version_span = parse_version(pair)?;
+                span.version_spans.push(version_span);
+            }
+            Rule::variable => span.version_spans.push(parse_variable(pair)),
+            Rule::unknown_variable => return Err(HookParseError::unknown_variable(pair.as_str())),
+            _ => (),
         }
     }
 
@@ -57,6 +81,17 @@ fn parse_version(pair: Pair<Rule>) -> Result<VersionSpan, HookParseError> {
             Rule::current_version => tokens.push_back(Token::Version),
             Rule::latest_version => tokens.push_back(Token::LatestVersion),
             Rule::ops => parse_operator(&mut tokens, pair.into_inner())?,
+            Rule::accessor => {
+                let field = match pair.into_inner().next().unwrap().as_rule() {
+                    Rule::major => VersionField::Major,
+                    Rule::minor => VersionField::Minor,
+                    Rule::patch => VersionField::Patch,
+                    Rule::pre => VersionField::PreRelease,
+                    Rule::build => VersionField::BuildMetadata,
+                    _ => unreachable!("Unexpected version accessor"),
+                };
+                tokens.push_back(Token::Accessor(field));
+            }
             Rule::pre_release => {
                 let identifiers = pair.into_inner().next().unwrap();
                 let semver_pre_release = Prerelease::new(identifiers.as_str())?;
@@ -77,6 +112,26 @@ fn parse_version(pair: Pair<Rule>) -> Result<VersionSpan, HookParseError> {
     })
 }
 
+fn parse_variable(pair: Pair<Rule>) -> VersionSpan {
+    let range = pair.as_span().start()..pair.as_span().end();
+    let token = match pair.into_inner().next().unwrap().as_rule() {
+        Rule::package => Token::Package,
+        Rule::packages => Token::Packages,
+        Rule::package_path => Token::PackagePath,
+        Rule::tag => Token::Tag,
+        Rule::version_tag => Token::VersionTag,
+        Rule::changelog_path => Token::ChangelogPath,
+        Rule::branch => Token::Branch,
+        Rule::commit_sha => Token::CommitSha,
+        _ => unreachable!("Unexpected hook variable"),
+    };
+
+    VersionSpan {
+        range,
+        tokens: VecDeque::from(vec![token]),
+    }
+}
+
 fn parse_operator(
     tokens: &mut VecDeque<Token>,
     pairs: Pairs<'_, Rule>,
@@ -99,7 +154,7 @@ fn parse_operator(
 mod test {
     use std::collections::VecDeque;
 
-    use crate::hook::parser::Token;
+    use crate::hook::parser::{Token, VersionField};
     use crate::hook::{parser, VersionSpan};
 
     use semver::Prerelease;
@@ -139,10 +194,80 @@ mod test {
             });
     }
 
+    #[test]
+    fn parse_version_accessor() {
+        let result = parser::parse("echo {{version.major}}");
+        assert_that!(result)
+            .is_ok()
+            .map(|span| &span.version_spans)
+            .contains(&VersionSpan {
+                range: 5..22,
+                tokens: VecDeque::from(vec![Token::Version, Token::Accessor(VersionField::Major)]),
+            });
+    }
+
+    #[test]
+    fn parse_variables() {
+        let result = parser::parse("cd {{package_path}} && echo {{package}}");
+        assert_that!(result)
+            .is_ok()
+            .map(|span| &span.version_spans)
+            .is_equal_to(vec![
+                VersionSpan {
+                    range: 3..19,
+                    tokens: VecDeque::from(vec![Token::PackagePath]),
+                },
+                VersionSpan {
+                    range: 28..39,
+                    tokens: VecDeque::from(vec![Token::Package]),
+                },
+            ]);
+    }
+
+    #[test]
+    fn unknown_variable_is_err() {
+        let result = parser::parse("echo {{versions}}");
+
+        assert_that!(result)
+            .is_err()
+            .matches(|err| err.to_string().contains("unknown variable `{{versions}}`"));
+    }
+
     #[test]
     fn invalid_dsl_is_err() {
         let result = parser::parse("the greatest {{+patch-pre.alpha0}}");
 
         assert_that!(result).is_err();
     }
+
+    #[test]
+    fn other_template_syntax_is_kept() {
+        let hooks = [
+            "docker inspect --format '{{.Id}}' cog",
+            "helm template --set image.tag={{ .Values.tag }}",
+            "echo {{ version }}",
+        ];
+
+        for hook in hooks {
+            let result = parser::parse(hook);
+
+            assert_that!(result)
+                .is_ok()
+                .map(|span| &span.version_spans)
+                .is_empty();
+        }
+    }
+
+    #[test]
+    fn other_template_syntax_is_kept_next_to_variables() {
+        let result = parser::parse("docker inspect --format '{{.Id}}' cog:{{version}}");
+
+        assert_that!(result)
+            .is_ok()
+            .map(|span| &span.version_spans)
+            .is_equal_to(vec![VersionSpan {
+                range: 38..49,
+                tokens: VecDeque::from(vec![Token::Version]),
+            }]);
+    }
 }
@@ -1,14 +1,39 @@
+//! Cocogitto is a set of tools for the conventional commit specification.
+//!
+//! Besides the `cog` command line, [`CocoGitto`] exposes an API returning [`CogError`]
+//! and result structs instead of printing reports or exiting the process:
+//!
+//! ```no_run
+//! use cocogitto::conventional::version::IncrementCommand;
+//! use cocogitto::CocoGitto;
+//!
+//! # fn main() -> Result<(), cocogitto::CogError> {
+//! let cocogitto = CocoGitto::open("path/to/repository")?;
+//!
+//! let report = cocogitto.check_commits(None, false)?;
+//! assert!(report.is_compliant());
+//!
+//! let plan = cocogitto.plan_bump(IncrementCommand::Auto, None)?;
+//! println!("{}", plan.changelog);
+//!
+//! let bump = cocogitto.apply_bump(&plan, None)?;
+//! println!("bumped to {}", bump.tag);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Progress messages go through the `log` crate, hook commands inherit the process stdout.
+
 use std::collections::HashMap;
+use std::path::Path;
 
 use anyhow::Result;
 
 use conventional_commit_parser::commit::{CommitType, ConventionalCommit};
 use conventional_commit_parser::parse_footers;
-use once_cell::sync::Lazy;
 
 use conventional::commit::{Commit, CommitConfig};
 use conventional::version::IncrementCommand;
-use error::BumpError;
 use git::repository::Repository;
 
 use settings::{HookType, Settings};
@@ -26,35 +51,51 @@ pub mod hook;
 pub mod log;
 pub mod settings;
 
+pub use command::bump::{AppliedBump, BumpPlan};
+pub use command::check::CheckOutcome;
+pub use error::CogError;
+
 pub type CommitsMetadata = HashMap<CommitType, CommitConfig>;
 
 pub const CONFIG_PATH: &str = "cog.toml";
 
-pub static SETTINGS: Lazy<Settings> = Lazy::new(|| {
-    if let Ok(repo) = Repository::open(".") {
-        Settings::get(&repo).unwrap_or_default()
-    } else {
-        Settings::default()
-    }
-});
-
-// This cannot be carried by `Cocogitto` struct since we need it to be available in `Changelog`,
-// `Commit` etc. Be sure that `CocoGitto::new` is called before using this  in order to bypass
-// unwrapping in case of error.
-pub static COMMITS_METADATA: Lazy<CommitsMetadata> = Lazy::new(|| SETTINGS.commit_types());
-
 #[derive(Debug)]
 pub struct CocoGitto {
     repository: Repository,
 }
 
 impl CocoGitto {
+    /// Open the repository in the current directory, reading its settings from the repository
+    /// configuration only.
     pub fn get() -> Result<Self> {
-        let repository = Repository::open(&std::env::current_dir()?)?;
-        let _settings = Settings::get(&repository)?;
-        let _changelog_path = settings::changelog_path();
+        Ok(Self::open(&std::env::current_dir()?)?)
+    }
+
+    /// Open the repository at `path`, reading its settings like [`CocoGitto::get`].
+    /// Use [`CocoGitto::with_settings`] and [`settings::try_load`] to read other config sources.
+    pub fn open<S: AsRef<Path> + ?Sized>(path: &S) -> Result<Self, CogError> {
+        let repository = Repository::open(path)?;
+        let settings = Settings::get(&repository)?;
+
+        Ok(CocoGitto {
+            repository: repository.with_settings(settings),
+        })
+    }
+
+    /// Open the repository at `path` using the given settings, no configuration file is read.
+    pub fn with_settings<S: AsRef<Path> + ?Sized>(
+        path: &S,
+        settings: Settings,
+    ) -> Result<Self, CogError> {
+        let repository = Repository::open(path)?;
+
+        Ok(CocoGitto {
+            repository: repository.with_settings(settings),
+        })
+    }
 
-        Ok(CocoGitto { repository })
+    pub fn settings(&self) -> &Settings {
+        self.repository.settings()
     }
 
     pub fn get_committer(&self) -> Result<String, Git2Error> {
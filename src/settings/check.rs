@@ -0,0 +1,422 @@
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use crate::conventional::changelog::template::TemplateKind;
+use crate::hook::Hook;
+use crate::settings::source::{config_files, manifest_layers, ConfigOrigin, ConfigSources};
+use crate::settings::{BumpProfile, HookConfig, Settings};
+
+use globset::Glob;
+
+/// A configuration problem found by `cog config check`
+#[derive(Debug)]
+pub struct ConfigDiagnostic {
+    pub origin: Option<ConfigOrigin>,
+    /// One based line and column of the faulty value in the origin file
+    pub position: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let path = self.origin.as_ref().and_then(ConfigOrigin::path);
+        match (path, self.position) {
+            (Some(path), Some((line, column))) => {
+                write!(
+                    f,
+                    "{}:{}:{}: {}",
+                    path.display(),
+                    line,
+                    column,
+                    self.message
+                )
+            }
+            (Some(path), None) => write!(f, "{}: {}", path.display(), self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// A loaded config file, used to locate faulty values
+struct SourceFile {
+    origin: ConfigOrigin,
+    content: String,
+}
+
+impl SourceFile {
+    /// Position of the first occurrence of `value` as a toml string, or as is
+    fn find(&self, value: &str) -> Option<(usize, usize)> {
+        let quoted = [format!("\"{value}\""), format!("'{value}'")];
+        let idx = quoted
+            .iter()
+            .find_map(|quoted| self.content.find(quoted.as_str()))
+            .or_else(|| self.content.find(value))?;
+
+        Some(line_col(&self.content, idx))
+    }
+}
+
+fn line_col(content: &str, idx: usize) -> (usize, usize) {
+    let before = &content[..idx];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rfind('\n')
+        .map(|newline| before[newline + 1..].chars().count())
+        .unwrap_or_else(|| before.chars().count())
+        + 1;
+    (line, column)
+}
+
+/// The field name of a serde "unknown field" error
+fn unknown_field(message: &str) -> Option<&str> {
+    message
+        .strip_prefix("unknown field `")
+        .and_then(|rest| rest.split_once('`'))
+        .map(|(field, _)| field)
+}
+
+/// Position of the first `key = ...` line, starting at line `from`
+fn find_key(content: &str, key: &str, from: usize) -> Option<(usize, usize)> {
+    content
+        .lines()
+        .enumerate()
+        .skip(from - 1)
+        .find_map(|(idx, line)| {
+            let trimmed = line.trim_start();
+            let is_key = trimmed
+                .strip_prefix(key)
+                .and_then(|rest| rest.chars().next())
+                .is_some_and(|next| next == '=' || next == '.' || next.is_whitespace());
+
+            is_key.then(|| (idx + 1, line.len() - trimmed.len() + 1))
+        })
+}
+
+/// Join multi-line error messages on a single line
+fn one_line(message: &str) -> String {
+    message
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(": ")
+}
+
+struct Checker {
+    files: Vec<SourceFile>,
+    diagnostics: Vec<ConfigDiagnostic>,
+}
+
+impl Checker {
+    /// Report a problem about `value`, located in the highest precedence file containing it
+    fn report(&mut self, value: &str, message: String) {
+        let located = self
+            .files
+            .iter()
+            .rev()
+            .find_map(|file| file.find(value).map(|position| (file, position)));
+
+        self.diagnostics.push(ConfigDiagnostic {
+            origin: located.map(|(file, _)| file.origin.clone()),
+            position: located.map(|(_, position)| position),
+            message,
+        });
+    }
+
+    fn check_hooks<'a>(&mut self, key: &str, hooks: impl IntoIterator<Item = &'a HookConfig>) {
+        for config in hooks {
+            let result = Hook::try_from(config).and_then(|hook| hook.validate());
+            if let Err(err) = result {
+                let command = match config {
+                    HookConfig::Command(command) => command,
+                    HookConfig::Detailed(entry) => &entry.command,
+                };
+
+                self.report(
+                    command,
+                    format!(
+                        "invalid hook `{}` in {}: {}",
+                        command,
+                        key,
+                        one_line(&format!("{err:#}"))
+                    ),
+                );
+            }
+        }
+    }
+
+    fn check_template(&mut self, key: &str, template: Option<&String>, repo_path: &Path) {
+        if let Some(template) = template {
+            if TemplateKind::from_arg(template).is_err() && !repo_path.join(template).exists() {
+                self.report(template, format!("{key}: template `{template}` not found"));
+            }
+        }
+    }
+}
+
+impl Settings {
+    /// Validate every config source and the merged settings, returning all problems found.
+    pub(crate) fn check(
+        repo_path: Option<&Path>,
+        sources: &ConfigSources,
+    ) -> Vec<ConfigDiagnostic> {
+        let mut checker = Checker {
+            files: vec![],
+            diagnostics: vec![],
+        };
+
+        for (origin, path) in config_files(repo_path, sources) {
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(err) => {
+                    checker.diagnostics.push(ConfigDiagnostic {
+                        origin: Some(origin),
+                        position: None,
+                        message: format!("failed to read config: {err}"),
+                    });
+                    continue;
+                }
+            };
+
+            // Deserializing the file on its own gives the position of syntax errors and unknown keys
+            if let Err(err) = toml::from_str::<Settings>(&content) {
+                let message = err.to_string();
+                let message = match message.rsplit_once(" at line ") {
+                    Some((message, _)) if err.line_col().is_some() => message.to_string(),
+                    _ => message,
+                };
+
+                let position = err.line_col().map(|(line, column)| (line + 1, column + 1));
+                // Unknown fields are reported at the start of their table, point at the key instead
+                let position = unknown_field(&message)
+                    .and_then(|key| find_key(&content, key, position.map_or(1, |(line, _)| line)))
+                    .or(position);
+
+                checker.diagnostics.push(ConfigDiagnostic {
+                    origin: Some(origin.clone()),
+                    position,
+                    message,
+                });
+            }
+
+            checker.files.push(SourceFile { origin, content });
+        }
+
+        if let Some(repo_path) = repo_path {
+            match manifest_layers(repo_path) {
+                Ok(layers) => {
+                    for layer in layers {
+                        if let Err(err) = layer.config.try_deserialize::<Settings>() {
+                            checker.diagnostics.push(ConfigDiagnostic {
+                                origin: Some(layer.origin),
+                                position: None,
+                                message: err.to_string(),
+                            });
+                        }
+                    }
+                }
+                Err(err) => checker.diagnostics.push(ConfigDiagnostic {
+                    origin: None,
+                    position: None,
+                    message: err.to_string(),
+                }),
+            }
+        }
+
+        if !checker.diagnostics.is_empty() {
+            return checker.diagnostics;
+        }
+
+        let settings = match Settings::load(repo_path, sources) {
+            Ok(settings) => settings,
+            Err(err) => {
+                checker.diagnostics.push(ConfigDiagnostic {
+                    origin: None,
+                    position: None,
+                    message: one_line(&err.to_string()),
+                });
+                return checker.diagnostics;
+            }
+        };
+
+        for pattern in &settings.branch_whitelist {
+            if let Err(err) = Glob::new(pattern) {
+                checker.report(
+                    pattern,
+                    format!("invalid branch_whitelist pattern `{pattern}`: {err}"),
+                );
+            }
+        }
+
+        let repo_path = repo_path.map(Path::to_path_buf).unwrap_or_default();
+        checker.check_template(
+            "changelog.template",
+            settings.changelog.template.as_ref(),
+            &repo_path,
+        );
+        checker.check_template(
+            "changelog.package_template",
+            settings.changelog.package_template.as_ref(),
+            &repo_path,
+        );
+
+        let global_hooks = [
+            ("pre_bump_hooks", &settings.pre_bump_hooks),
+            ("post_bump_hooks", &settings.post_bump_hooks),
+            ("pre_package_bump_hooks", &settings.pre_package_bump_hooks),
+            ("post_package_bump_hooks", &settings.post_package_bump_hooks),
+            (
+                "pre_package_changelog_hooks",
+                &settings.pre_package_changelog_hooks,
+            ),
+            (
+                "post_package_changelog_hooks",
+                &settings.post_package_changelog_hooks,
+            ),
+            (
+                "pre_package_commit_hooks",
+                &settings.pre_package_commit_hooks,
+            ),
+            ("pre_package_tag_hooks", &settings.pre_package_tag_hooks),
+            (
+                "on_package_failure_hooks",
+                &settings.on_package_failure_hooks,
+            ),
+            ("pre_changelog_hooks", &settings.pre_changelog_hooks),
+            ("post_changelog_hooks", &settings.post_changelog_hooks),
+            ("pre_commit_hooks", &settings.pre_commit_hooks),
+            ("pre_tag_hooks", &settings.pre_tag_hooks),
+            ("on_failure_hooks", &settings.on_failure_hooks),
+        ];
+
+        for (key, hooks) in global_hooks {
+            checker.check_hooks(key, hooks);
+        }
+
+        for (name, profile) in &settings.bump_profiles {
+            checker.check_hooks(&format!("bump_profiles.{name}"), profile.hooks());
+        }
+
+        for (name, package) in &settings.packages {
+            let key = format!("packages.{name}");
+            if !repo_path.join(&package.path).exists() {
+                let path = package.path.display().to_string();
+                checker.report(
+                    &path,
+                    format!("{key}: package path `{path}` does not exist"),
+                );
+            }
+
+            let package_hooks = package
+                .pre_bump_hooks
+                .iter()
+                .flatten()
+                .chain(package.post_bump_hooks.iter().flatten())
+                .chain(package.pre_changelog_hooks.iter().flatten())
+                .chain(package.post_changelog_hooks.iter().flatten())
+                .chain(package.pre_commit_hooks.iter().flatten())
+                .chain(package.pre_tag_hooks.iter().flatten())
+                .chain(package.on_failure_hooks.iter().flatten());
+            checker.check_hooks(&key, package_hooks);
+
+            for (profile_name, profile) in &package.bump_profiles {
+                checker.check_hooks(
+                    &format!("{key}.bump_profiles.{profile_name}"),
+                    profile.hooks(),
+                );
+            }
+        }
+
+        checker.diagnostics
+    }
+}
+
+impl BumpProfile {
+    fn hooks(&self) -> impl Iterator<Item = &HookConfig> {
+        self.pre_bump_hooks
+            .iter()
+            .chain(&self.post_bump_hooks)
+            .chain(&self.pre_changelog_hooks)
+            .chain(&self.post_changelog_hooks)
+            .chain(&self.pre_commit_hooks)
+            .chain(&self.pre_tag_hooks)
+            .chain(&self.on_failure_hooks)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::settings::source::ConfigSources;
+    use crate::settings::Settings;
+
+    use anyhow::Result;
+    use sealed_test::prelude::*;
+    use speculoos::prelude::*;
+
+    #[sealed_test(env = [("XDG_CONFIG_HOME", "user_config")])]
+    fn valid_config_has_no_diagnostic() -> Result<()> {
+        // Arrange
+        fs::create_dir("one")?;
+        fs::write(
+            "cog.toml",
+            "branch_whitelist = [\"main\"]\npre_bump_hooks = [\"echo {{version}}\"]\n\n[packages.one]\npath = \"one\"\n",
+        )?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let diagnostics = Settings::check(Some(&repo_path), &ConfigSources::default());
+
+        // Assert
+        assert_that!(diagnostics).is_empty();
+        Ok(())
+    }
+
+    #[sealed_test(env = [("XDG_CONFIG_HOME", "user_config")])]
+    fn unknown_key_is_reported_with_position() -> Result<()> {
+        // Arrange
+        fs::write("cog.toml", "tag_prefix = \"v\"\ntag_prefixes = \"v\"\n")?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let diagnostics = Settings::check(Some(&repo_path), &ConfigSources::default());
+
+        // Assert
+        assert_that!(diagnostics).has_length(1);
+        assert_that!(diagnostics[0].position).is_equal_to(Some((2, 1)));
+        assert_that!(diagnostics[0].message.as_str()).contains("unknown field `tag_prefixes`");
+        Ok(())
+    }
+
+    #[sealed_test(env = [("XDG_CONFIG_HOME", "user_config")])]
+    fn invalid_values_are_reported_with_position() -> Result<()> {
+        // Arrange
+        fs::write(
+            "cog.toml",
+            "branch_whitelist = [\"[main\"]\npost_bump_hooks = [\"echo {{unknown}}\"]\n\n[changelog]\ntemplate = \"missing.tera\"\n\n[packages.one]\npath = \"one\"\n",
+        )?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let diagnostics = Settings::check(Some(&repo_path), &ConfigSources::default());
+
+        // Assert
+        let diagnostics: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.position, diagnostic.message.as_str()))
+            .collect();
+
+        assert_that!(diagnostics).has_length(4);
+        assert_that!(diagnostics[0].0).is_equal_to(Some((1, 21)));
+        assert_that!(diagnostics[0].1).contains("invalid branch_whitelist pattern `[main`");
+        assert_that!(diagnostics[1].0).is_equal_to(Some((5, 12)));
+        assert_that!(diagnostics[1].1).contains("template `missing.tera` not found");
+        assert_that!(diagnostics[2].0).is_equal_to(Some((2, 20)));
+        assert_that!(diagnostics[2].1).contains("invalid hook `echo {{unknown}}`");
+        assert_that!(diagnostics[3].0).is_equal_to(Some((8, 8)));
+        assert_that!(diagnostics[3].1).contains("package path `one` does not exist");
+        Ok(())
+    }
+}
@@ -3,59 +3,119 @@ use std::path::PathBuf;
 
 use crate::conventional::commit::CommitConfig;
 use crate::git::repository::Repository;
-use crate::{CommitsMetadata, CONFIG_PATH, SETTINGS};
+use crate::CommitsMetadata;
 
 use crate::conventional::changelog::error::ChangelogError;
-use crate::conventional::changelog::template::{RemoteContext, Template};
+use crate::conventional::changelog::template::{
+    parse_remote_url, RemoteContext, RemotePlatform, Template,
+};
+use crate::conventional::changelog::CHANGELOG_SEPARATOR;
 use crate::git::hook::Hooks;
-use crate::settings::error::SettingError;
-use config::{Config, File};
 use conventional_commit_parser::commit::CommitType;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 type CommitsMetadataSettings = HashMap<String, CommitConfig>;
 pub(crate) type AuthorSettings = Vec<AuthorSetting>;
 
+mod check;
 mod error;
+mod source;
+
+pub use check::ConfigDiagnostic;
+pub use error::SettingError;
+pub use source::{ConfigEntry, ConfigOrigin, ConfigSources};
 
 #[derive(Copy, Clone)]
 pub enum HookType {
     PreBump,
     PostBump,
+    PreChangelog,
+    PostChangelog,
+    PreCommit,
+    PreTag,
+    OnFailure,
 }
 
-#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Default)]
+/// Cocogitto configuration, usually found in `cog.toml`
+#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq, Default, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Settings {
+    /// Only check commits since the latest tag with `cog check` and `cog edit`
     #[serde(default)]
     pub from_latest_tag: bool,
+    /// Ignore merge commit messages when checking commits
     #[serde(default)]
     pub ignore_merge_commits: bool,
+    /// Parse the conventional commits squashed into a single commit message
+    #[serde(default)]
+    pub parse_squash_commits: bool,
+    /// Separator between the package name and its version in monorepo tags
     #[serde(default)]
     pub monorepo_version_separator: Option<String>,
+    /// Glob patterns of the branches `cog bump` is allowed on
     #[serde(default)]
     pub branch_whitelist: Vec<String>,
+    /// Prefix of version tags, ex: `v`
     pub tag_prefix: Option<String>,
+    /// Run before the version commit is created
+    #[serde(default)]
+    pub pre_bump_hooks: Vec<HookConfig>,
+    /// Run after the version commit and tag are created
+    #[serde(default)]
+    pub post_bump_hooks: Vec<HookConfig>,
+    /// Default `pre_bump_hooks` of monorepo packages
+    #[serde(default)]
+    pub pre_package_bump_hooks: Vec<HookConfig>,
+    /// Default `post_bump_hooks` of monorepo packages
+    #[serde(default)]
+    pub post_package_bump_hooks: Vec<HookConfig>,
+    /// Default `pre_changelog_hooks` of monorepo packages
+    #[serde(default)]
+    pub pre_package_changelog_hooks: Vec<HookConfig>,
+    /// Default `post_changelog_hooks` of monorepo packages
+    #[serde(default)]
+    pub post_package_changelog_hooks: Vec<HookConfig>,
+    /// Default `pre_commit_hooks` of monorepo packages
+    #[serde(default)]
+    pub pre_package_commit_hooks: Vec<HookConfig>,
+    /// Default `pre_tag_hooks` of monorepo packages
+    #[serde(default)]
+    pub pre_package_tag_hooks: Vec<HookConfig>,
+    /// Default `on_failure_hooks` of monorepo packages
+    #[serde(default)]
+    pub on_package_failure_hooks: Vec<HookConfig>,
+    /// Run before the changelog is written
     #[serde(default)]
-    pub pre_bump_hooks: Vec<String>,
+    pub pre_changelog_hooks: Vec<HookConfig>,
+    /// Run after the changelog is written
     #[serde(default)]
-    pub post_bump_hooks: Vec<String>,
+    pub post_changelog_hooks: Vec<HookConfig>,
+    /// Run before the version commit is created, after `pre_bump_hooks`
     #[serde(default)]
-    pub pre_package_bump_hooks: Vec<String>,
+    pub pre_commit_hooks: Vec<HookConfig>,
+    /// Run before the version tag is created
     #[serde(default)]
-    pub post_package_bump_hooks: Vec<String>,
+    pub pre_tag_hooks: Vec<HookConfig>,
+    /// Run when one of the bump hooks fails
+    #[serde(default)]
+    pub on_failure_hooks: Vec<HookConfig>,
+    /// Custom commit types, or overrides of the default ones
     #[serde(default)]
     pub commit_types: CommitsMetadataSettings,
+    /// Changelog generation settings
     #[serde(default)]
     pub changelog: Changelog,
+    /// Named sets of hooks, selected with `cog bump --hook-profile`
     #[serde(default)]
     pub bump_profiles: HashMap<String, BumpProfile>,
+    /// Monorepo packages, by name
     #[serde(default)]
     pub packages: HashMap<String, MonoRepoPackage>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq, JsonSchema)]
 #[serde(deny_unknown_fields, default)]
 pub struct MonoRepoPackage {
     /// The package path, relative to the repository root dir.
@@ -67,9 +127,19 @@ pub struct MonoRepoPackage {
     /// the global monorepo version when using `cog bump --auto`
     pub public_api: bool,
     /// Overrides `pre_package_bump_hooks`
-    pub pre_bump_hooks: Option<Vec<String>>,
+    pub pre_bump_hooks: Option<Vec<HookConfig>>,
     /// Overrides `post_package_bump_hooks`
-    pub post_bump_hooks: Option<Vec<String>>,
+    pub post_bump_hooks: Option<Vec<HookConfig>>,
+    /// Run before the package changelog is written, overrides `pre_package_changelog_hooks`
+    pub pre_changelog_hooks: Option<Vec<HookConfig>>,
+    /// Run after the package changelog is written, overrides `post_package_changelog_hooks`
+    pub post_changelog_hooks: Option<Vec<HookConfig>>,
+    /// Run before the version commit is created, overrides `pre_package_commit_hooks`
+    pub pre_commit_hooks: Option<Vec<HookConfig>>,
+    /// Run before the package tag is created, overrides `pre_package_tag_hooks`
+    pub pre_tag_hooks: Option<Vec<HookConfig>>,
+    /// Run when one of the package hooks fails, overrides `on_package_failure_hooks`
+    pub on_failure_hooks: Option<Vec<HookConfig>>,
     /// Custom profile to override `pre_bump_hooks`, `post_bump_hooks`
     pub bump_profiles: HashMap<String, BumpProfile>,
 }
@@ -81,6 +151,11 @@ impl Default for MonoRepoPackage {
             changelog_path: None,
             pre_bump_hooks: None,
             post_bump_hooks: None,
+            pre_changelog_hooks: None,
+            post_changelog_hooks: None,
+            pre_commit_hooks: None,
+            pre_tag_hooks: None,
+            on_failure_hooks: None,
             bump_profiles: Default::default(),
             public_api: true,
         }
@@ -94,18 +169,45 @@ impl MonoRepoPackage {
             .map(PathBuf::from)
             .unwrap_or_else(|| self.path.join("CHANGELOG.md"))
     }
+
+    /// The package hooks, falling back to the package hooks of the given settings
+    pub(crate) fn hooks<'a>(&'a self, settings: &'a Settings) -> PackageHooks<'a> {
+        PackageHooks {
+            package: self,
+            settings,
+        }
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq, JsonSchema)]
 #[serde(deny_unknown_fields, default)]
 pub struct Changelog {
+    /// Changelog template, either a predefined one (`default`, `remote`, `full_hash`) or a path
     pub template: Option<String>,
+    /// Template of monorepo package changelogs
     pub package_template: Option<String>,
+    /// Remote repository domain, ex: `github.com`
     pub remote: Option<String>,
+    /// The platform hosting the remote repository, guessed from `remote` if not set
+    pub platform: Option<RemotePlatform>,
+    /// Where to write the changelog
     pub path: PathBuf,
+    /// Remote repository owner
     pub owner: Option<String>,
+    /// Remote repository name
     pub repository: Option<String>,
+    /// Map git signatures to remote platform usernames
     pub authors: AuthorSettings,
+    /// Issue trackers used to link references in commit messages
+    pub issue_trackers: Vec<IssueTracker>,
+    /// Header of newly created changelogs, a tera template with the same remote variables
+    /// as the changelog templates, and `package_name` for package changelogs
+    pub header: Option<String>,
+    /// Footer of newly created changelogs, a tera template like `header`
+    pub footer: Option<String>,
+    /// Line separating the header, the releases and the footer of changelogs,
+    /// new releases are inserted after the first one. Defaults to `- - -`
+    pub separator: Option<String>,
 }
 
 impl Default for Changelog {
@@ -114,62 +216,155 @@ impl Default for Changelog {
             template: None,
             package_template: None,
             remote: None,
+            platform: None,
             path: PathBuf::from("CHANGELOG.md"),
             owner: None,
             repository: None,
             authors: vec![],
+            issue_trackers: vec![],
+            header: None,
+            footer: None,
+            separator: None,
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq)]
+impl Changelog {
+    /// The configured changelog separator, or the default `- - -`
+    pub fn separator(&self) -> &str {
+        self.separator.as_deref().unwrap_or(CHANGELOG_SEPARATOR)
+    }
+}
+
+/// A changelog author
+#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct AuthorSetting {
+    /// The git author name
     pub signature: String,
+    /// The author username on the remote platform
     pub username: String,
 }
 
-pub fn commit_username(author: &str) -> Option<&'static str> {
-    SETTINGS
-        .changelog
-        .authors
-        .iter()
-        .find(|author_map| author_map.signature == author)
-        .map(|author| author.username.as_str())
+/// An issue tracker used to link references such as `#123` or `JIRA-456` in changelogs.
+#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct IssueTracker {
+    /// The reference prefix, ex: `#` or `JIRA-`
+    pub prefix: String,
+    /// The issue url, `{{id}}` is replaced with the reference number
+    pub url: String,
 }
 
-pub fn changelog_path() -> &'static PathBuf {
-    &SETTINGS.changelog.path
+/// Every value of the merged configuration along with its origin,
+/// using the repository in the current directory if any
+pub fn config_entries(sources: &ConfigSources) -> anyhow::Result<Vec<ConfigEntry>> {
+    let repository = Repository::open(".").ok();
+    let repo_path = repository.as_ref().and_then(Repository::get_repo_dir);
+    Settings::entries(repo_path, sources).map_err(Into::into)
 }
 
-#[derive(Debug, Deserialize, Serialize, Default, Eq, PartialEq)]
+/// JSON Schema of the configuration, used by editors to complete and validate `cog.toml`
+pub fn json_schema() -> String {
+    let schema = schemars::schema_for!(Settings);
+    serde_json::to_string_pretty(&schema).expect("the schema serializes to json")
+}
+
+/// Validate the configuration of the repository in the current directory, if any
+pub fn check_config(sources: &ConfigSources) -> Vec<ConfigDiagnostic> {
+    let repository = Repository::open(".").ok();
+    let repo_path = repository.as_ref().and_then(Repository::get_repo_dir);
+    Settings::check(repo_path, sources)
+}
+
+/// Load the configuration of the repository in the current directory, if any
+pub fn try_load(sources: &ConfigSources) -> anyhow::Result<Settings> {
+    let repository = Repository::open(".").ok();
+    let repo_path = repository.as_ref().and_then(Repository::get_repo_dir);
+    Settings::load(repo_path, sources).map_err(Into::into)
+}
+
+/// A bump hook, either a plain command or a table with execution options.
+#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum HookConfig {
+    Command(String),
+    Detailed(HookEntry),
+}
+
+/// A bump hook command and its execution options.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, Eq, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct HookEntry {
+    /// The command to run, supports the hook version DSL
+    pub command: String,
+    /// The shell used to run the command, defaults to `sh`.
+    /// A single word shell is invoked with `-c`, otherwise the command is appended to its arguments
+    pub shell: Option<String>,
+    /// Working directory, relative to the package path if any, or the repository root
+    pub cwd: Option<PathBuf>,
+    /// Kill the hook after the given amount of seconds
+    pub timeout: Option<u64>,
+    /// How many times a failing hook is retried
+    #[serde(default)]
+    pub retries: u32,
+    /// Do not abort the bump when the hook fails
+    #[serde(default)]
+    pub allow_failure: bool,
+    /// Additional environment variables for the hook process
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Only run the hook for the given kind of bump
+    pub when: Option<HookCondition>,
+    /// Only run the hook when the current branch matches this glob pattern
+    pub when_branch: Option<String>,
+    /// Consecutive hooks sharing the same parallel group run concurrently
+    pub parallel: Option<String>,
+}
+
+/// The kind of bump a hook is restricted to
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HookCondition {
+    Prerelease,
+    Major,
+    Minor,
+    Patch,
+}
+
+/// A named set of hooks overriding the default ones
+#[derive(Debug, Deserialize, Serialize, Clone, Default, Eq, PartialEq, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct BumpProfile {
     #[serde(default)]
-    pub pre_bump_hooks: Vec<String>,
+    pub pre_bump_hooks: Vec<HookConfig>,
+    #[serde(default)]
+    pub post_bump_hooks: Vec<HookConfig>,
+    #[serde(default)]
+    pub pre_changelog_hooks: Vec<HookConfig>,
+    #[serde(default)]
+    pub post_changelog_hooks: Vec<HookConfig>,
+    #[serde(default)]
+    pub pre_commit_hooks: Vec<HookConfig>,
+    #[serde(default)]
+    pub pre_tag_hooks: Vec<HookConfig>,
     #[serde(default)]
-    pub post_bump_hooks: Vec<String>,
+    pub on_failure_hooks: Vec<HookConfig>,
 }
 
 impl Settings {
     // Fails only if config exists and is malformed
     pub(crate) fn get(repository: &Repository) -> Result<Self, SettingError> {
-        match repository.get_repo_dir() {
-            Some(repo_path) => {
-                let settings_path = repo_path.join(CONFIG_PATH);
-                if settings_path.exists() {
-                    Config::builder()
-                        .add_source(File::from(settings_path))
-                        .build()
-                        .map_err(SettingError::from)?
-                        .try_deserialize()
-                        .map_err(SettingError::from)
-                } else {
-                    Ok(Settings::default())
-                }
-            }
-            None => Ok(Settings::default()),
-        }
+        Settings::load(repository.get_repo_dir(), &ConfigSources::default())
+    }
+
+    /// The remote platform username of a commit author, from the `changelog.authors` settings
+    pub fn commit_username(&self, author: &str) -> Option<&str> {
+        self.changelog
+            .authors
+            .iter()
+            .find(|author_map| author_map.signature == author)
+            .map(|author| author.username.as_str())
     }
 
     pub fn commit_types(&self) -> CommitsMetadata {
@@ -209,25 +404,47 @@ impl Settings {
         default_types
     }
 
-    pub fn get_template_context(&self) -> Option<RemoteContext> {
-        let remote = self.changelog.remote.as_ref().cloned();
+    /// Fill the changelog remote, owner and repository that are not configured
+    /// from the `origin` remote url of `repository`, if any.
+    pub(crate) fn infer_remote(&mut self, repository: &Repository) {
+        let changelog = &mut self.changelog;
+        if changelog.remote.is_some() && changelog.repository.is_some() && changelog.owner.is_some()
+        {
+            return;
+        }
 
-        let repository = self.changelog.repository.as_ref().cloned();
+        let origin = repository
+            .get_remote_url("origin")
+            .and_then(|url| parse_remote_url(&url));
 
-        let owner = self.changelog.owner.as_ref().cloned();
+        if let Some((remote, owner, repository)) = origin {
+            changelog.remote.get_or_insert(remote);
+            changelog.owner.get_or_insert(owner);
+            changelog.repository.get_or_insert(repository);
+        }
+    }
 
-        RemoteContext::try_new(remote, repository, owner)
+    /// Remote context from the changelog settings. Omitted values are inferred
+    /// from the `origin` remote url once the settings are attached to a repository.
+    pub fn get_template_context(&self) -> Result<Option<RemoteContext>, ChangelogError> {
+        let changelog = &self.changelog;
+        Ok(RemoteContext::try_new(
+            changelog.remote.clone(),
+            changelog.repository.clone(),
+            changelog.owner.clone(),
+        )?
+        .map(|context| context.with_platform(changelog.platform)))
     }
 
     pub fn get_changelog_template(&self) -> Result<Template, ChangelogError> {
-        let context = self.get_template_context();
+        let context = self.get_template_context()?;
         let template = self.changelog.template.as_deref().unwrap_or("default");
 
         Template::from_arg(template, context)
     }
 
     pub fn get_package_changelog_template(&self) -> Result<Template, ChangelogError> {
-        let context = self.get_template_context();
+        let context = self.get_template_context()?;
         let template = self
             .changelog
             .package_template
@@ -244,7 +461,7 @@ impl Settings {
     }
 
     pub fn get_monorepo_changelog_template(&self) -> Result<Template, ChangelogError> {
-        let context = self.get_template_context();
+        let context = self.get_template_context()?;
         let template = self
             .changelog
             .template
@@ -278,29 +495,110 @@ impl Hooks for Settings {
         &self.bump_profiles
     }
 
-    fn pre_bump_hooks(&self) -> &Vec<String> {
+    fn pre_bump_hooks(&self) -> &Vec<HookConfig> {
         &self.pre_bump_hooks
     }
 
-    fn post_bump_hooks(&self) -> &Vec<String> {
+    fn post_bump_hooks(&self) -> &Vec<HookConfig> {
         &self.post_bump_hooks
     }
+
+    fn pre_changelog_hooks(&self) -> &Vec<HookConfig> {
+        &self.pre_changelog_hooks
+    }
+
+    fn post_changelog_hooks(&self) -> &Vec<HookConfig> {
+        &self.post_changelog_hooks
+    }
+
+    fn pre_commit_hooks(&self) -> &Vec<HookConfig> {
+        &self.pre_commit_hooks
+    }
+
+    fn pre_tag_hooks(&self) -> &Vec<HookConfig> {
+        &self.pre_tag_hooks
+    }
+
+    fn on_failure_hooks(&self) -> &Vec<HookConfig> {
+        &self.on_failure_hooks
+    }
 }
 
-impl Hooks for MonoRepoPackage {
+/// The hooks of a monorepo package, each stage defaults to the matching package hooks
+/// of the settings, ex: `pre_bump_hooks` to `pre_package_bump_hooks`.
+pub(crate) struct PackageHooks<'a> {
+    package: &'a MonoRepoPackage,
+    settings: &'a Settings,
+}
+
+impl Hooks for PackageHooks<'_> {
     fn bump_profiles(&self) -> &HashMap<String, BumpProfile> {
-        &self.bump_profiles
+        &self.package.bump_profiles
     }
 
-    fn pre_bump_hooks(&self) -> &Vec<String> {
-        self.pre_bump_hooks
+    fn pre_bump_hooks(&self) -> &Vec<HookConfig> {
+        self.package
+            .pre_bump_hooks
             .as_ref()
-            .unwrap_or(&SETTINGS.pre_package_bump_hooks)
+            .unwrap_or(&self.settings.pre_package_bump_hooks)
     }
 
-    fn post_bump_hooks(&self) -> &Vec<String> {
-        self.post_bump_hooks
+    fn post_bump_hooks(&self) -> &Vec<HookConfig> {
+        self.package
+            .post_bump_hooks
             .as_ref()
-            .unwrap_or(&SETTINGS.post_package_bump_hooks)
+            .unwrap_or(&self.settings.post_package_bump_hooks)
+    }
+
+    fn pre_changelog_hooks(&self) -> &Vec<HookConfig> {
+        self.package
+            .pre_changelog_hooks
+            .as_ref()
+            .unwrap_or(&self.settings.pre_package_changelog_hooks)
+    }
+
+    fn post_changelog_hooks(&self) -> &Vec<HookConfig> {
+        self.package
+            .post_changelog_hooks
+            .as_ref()
+            .unwrap_or(&self.settings.post_package_changelog_hooks)
+    }
+
+    fn pre_commit_hooks(&self) -> &Vec<HookConfig> {
+        self.package
+            .pre_commit_hooks
+            .as_ref()
+            .unwrap_or(&self.settings.pre_package_commit_hooks)
+    }
+
+    fn pre_tag_hooks(&self) -> &Vec<HookConfig> {
+        self.package
+            .pre_tag_hooks
+            .as_ref()
+            .unwrap_or(&self.settings.pre_package_tag_hooks)
+    }
+
+    fn on_failure_hooks(&self) -> &Vec<HookConfig> {
+        self.package
+            .on_failure_hooks
+            .as_ref()
+            .unwrap_or(&self.settings.on_package_failure_hooks)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::settings::json_schema;
+
+    use pretty_assertions::assert_eq;
+
+    const SCHEMA_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/docs/cog-schema.json");
+
+    #[test]
+    fn json_schema_is_up_to_date() {
+        let committed = std::fs::read_to_string(SCHEMA_PATH).expect("docs/cog-schema.json exists");
+
+        // Regenerate with `cog config schema > docs/cog-schema.json`
+        assert_eq!(committed.trim_end(), json_schema());
     }
 }
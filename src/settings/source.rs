@@ -0,0 +1,719 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::settings::error::SettingError;
+use crate::settings::Settings;
+use crate::CONFIG_PATH;
+
+use config::{Config, ConfigError, File, FileFormat, Map, Source, Value, ValueKind};
+use log::warn;
+
+/// Settings that can be overridden with a `COG_<KEY>` environment variable, ex: `COG_TAG_PREFIX`.
+/// Only these keys are read from the environment so variables exported to hooks
+/// (`COG_VERSION`, `COG_CHANGELOG` ...) are never mistaken for settings.
+const ENV_KEYS: &[&str] = &[
+    "from_latest_tag",
+    "ignore_merge_commits",
+    "parse_squash_commits",
+    "monorepo_version_separator",
+    "tag_prefix",
+    "branch_whitelist",
+    "changelog.path",
+    "changelog.template",
+    "changelog.package_template",
+    "changelog.remote",
+    "changelog.platform",
+    "changelog.owner",
+    "changelog.repository",
+];
+
+/// List settings read from the environment as comma separated values
+const ENV_LIST_KEYS: &[&str] = &["branch_whitelist"];
+
+/// Dedicated repository config files, by decreasing precedence.
+/// Those take precedence over the `Cargo.toml` and `package.json` sections.
+const REPOSITORY_CONFIG_FILES: &[&str] = &[CONFIG_PATH, ".cog.toml", ".config/cog.toml"];
+
+/// Cargo manifest tables holding cog settings, by decreasing precedence
+const CARGO_METADATA_KEYS: &[&str] = &["package.metadata.cog", "workspace.metadata.cog"];
+
+/// Skip the user config file when set to a non empty value
+const NO_USER_CONFIG_VAR: &str = "COG_NO_USER_CONFIG";
+
+/// The configuration sources read along with the repository settings.
+/// The default value reads the repository settings only.
+#[derive(Debug, Default, Clone)]
+pub struct ConfigSources {
+    /// The user config file, ex: `$XDG_CONFIG_HOME/cog/cog.toml`
+    pub user_config: Option<PathBuf>,
+    /// A config file taking precedence over the user and repository ones, ex: `cog --config <PATH>`
+    pub config_path: Option<PathBuf>,
+    /// Environment variables as `(name, value)` pairs, only the `COG_<KEY>` settings are read
+    pub env: Vec<(String, String)>,
+}
+
+impl ConfigSources {
+    /// The sources used by the `cog` command line: the user config file unless
+    /// `COG_NO_USER_CONFIG` is set, `config_path` and the `COG_*` environment variables.
+    pub fn from_env(config_path: Option<PathBuf>) -> Self {
+        ConfigSources {
+            user_config: user_config_path(),
+            config_path,
+            env: std::env::vars_os()
+                .filter_map(|(var, value)| {
+                    Some((var.into_string().ok()?, value.into_string().ok()?))
+                })
+                .filter(|(var, _)| var.starts_with("COG_"))
+                .collect(),
+        }
+    }
+}
+
+/// Where a configuration value comes from
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ConfigOrigin {
+    Default,
+    /// `$XDG_CONFIG_HOME/cog/cog.toml`, ignored when `COG_NO_USER_CONFIG` is set
+    User(PathBuf),
+    /// `cog.toml`, `.cog.toml` or `.config/cog.toml` in the repository
+    Repository(PathBuf),
+    /// A section of a `Cargo.toml` or `package.json` manifest
+    Manifest(PathBuf, &'static str),
+    /// The file given with `--config`
+    Custom(PathBuf),
+    /// A `COG_*` environment variable
+    Environment(String),
+}
+
+impl Display for ConfigOrigin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigOrigin::Default => write!(f, "default"),
+            ConfigOrigin::User(path) => write!(f, "user config {}", path.display()),
+            ConfigOrigin::Repository(path) => write!(f, "repository config {}", path.display()),
+            ConfigOrigin::Manifest(path, key) => write!(f, "{} in {}", key, path.display()),
+            ConfigOrigin::Custom(path) => write!(f, "--config {}", path.display()),
+            ConfigOrigin::Environment(var) => write!(f, "environment variable {}", var),
+        }
+    }
+}
+
+/// A single configuration value of the merged settings
+#[derive(Debug)]
+pub struct ConfigEntry {
+    /// The dotted key path, ex: `changelog.path`
+    pub key: String,
+    pub value: toml::Value,
+    pub origin: ConfigOrigin,
+}
+
+impl Display for ConfigEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} = {} # {}",
+            self.key,
+            inline(&self.value),
+            self.origin
+        )
+    }
+}
+
+impl ConfigOrigin {
+    /// The file this origin refers to, if any
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            ConfigOrigin::User(path)
+            | ConfigOrigin::Repository(path)
+            | ConfigOrigin::Manifest(path, _)
+            | ConfigOrigin::Custom(path) => Some(path),
+            ConfigOrigin::Default | ConfigOrigin::Environment(_) => None,
+        }
+    }
+}
+
+pub(super) struct ConfigLayer {
+    pub(super) origin: ConfigOrigin,
+    pub(super) config: Config,
+}
+
+fn user_config_path() -> Option<PathBuf> {
+    if std::env::var_os(NO_USER_CONFIG_VAR).is_some_and(|value| !value.is_empty()) {
+        return None;
+    }
+
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|config_dir| config_dir.join("cog").join(CONFIG_PATH))
+}
+
+fn file_layer(origin: ConfigOrigin, path: &Path) -> Result<ConfigLayer, ConfigError> {
+    let config = Config::builder().add_source(File::from(path)).build()?;
+
+    Ok(ConfigLayer { origin, config })
+}
+
+fn manifest_layer(
+    path: PathBuf,
+    key: &'static str,
+    table: toml::Value,
+) -> Result<ConfigLayer, ConfigError> {
+    let content = toml::to_string(&table).map_err(|err| ConfigError::Foreign(Box::new(err)))?;
+    let config = Config::builder()
+        .add_source(File::from_str(&content, FileFormat::Toml))
+        .build()?;
+
+    Ok(ConfigLayer {
+        origin: ConfigOrigin::Manifest(path, key),
+        config,
+    })
+}
+
+fn cargo_metadata_layers(repo_path: &Path) -> Result<Vec<ConfigLayer>, ConfigError> {
+    let path = repo_path.join("Cargo.toml");
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let content = fs::read_to_string(&path).map_err(|err| ConfigError::Foreign(Box::new(err)))?;
+    let manifest: toml::Value = match toml::from_str(&content) {
+        Ok(manifest) => manifest,
+        // A broken manifest is only our problem if it holds cog settings
+        Err(err) if mentions_cog_metadata(&content) => {
+            return Err(ConfigError::FileParse {
+                uri: Some(path.display().to_string()),
+                cause: Box::new(err),
+            })
+        }
+        Err(err) => {
+            warn!("Ignoring unparsable {}: {err}", path.display());
+            return Ok(vec![]);
+        }
+    };
+
+    let mut layers = vec![];
+    for key in CARGO_METADATA_KEYS {
+        let table = key
+            .split('.')
+            .try_fold(&manifest, |value, key| value.get(key));
+
+        if let Some(table) = table {
+            layers.push(manifest_layer(path.clone(), key, table.clone())?);
+        }
+    }
+
+    Ok(layers)
+}
+
+/// Whether a Cargo manifest seems to hold a `metadata.cog` table, even if it does not parse
+fn mentions_cog_metadata(content: &str) -> bool {
+    content.lines().any(|line| {
+        let line = line.trim_start();
+        line.contains("metadata.cog")
+            || line
+                .strip_prefix("cog")
+                .is_some_and(|rest| rest.trim_start().starts_with(['=', '.']))
+    })
+}
+
+fn package_json_layer(repo_path: &Path) -> Result<Option<ConfigLayer>, ConfigError> {
+    let path = repo_path.join("package.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path).map_err(|err| ConfigError::Foreign(Box::new(err)))?;
+    let manifest: serde_json::Value = match serde_json::from_str(&content) {
+        Ok(manifest) => manifest,
+        Err(err) if content.contains("\"cog\"") => {
+            return Err(ConfigError::FileParse {
+                uri: Some(path.display().to_string()),
+                cause: Box::new(err),
+            })
+        }
+        Err(err) => {
+            warn!("Ignoring unparsable {}: {err}", path.display());
+            return Ok(None);
+        }
+    };
+
+    match manifest.get("cog") {
+        Some(cog) => {
+            let table = toml::Value::try_from(cog).map_err(|err| ConfigError::FileParse {
+                uri: Some(path.display().to_string()),
+                cause: Box::new(err),
+            })?;
+            manifest_layer(path, "cog", table).map(Some)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Cog settings found in the repository manifests
+pub(super) fn manifest_layers(repo_path: &Path) -> Result<Vec<ConfigLayer>, ConfigError> {
+    let mut layers = cargo_metadata_layers(repo_path)?;
+    layers.extend(package_json_layer(repo_path)?);
+    Ok(layers)
+}
+
+/// Every toml config file in use, by increasing precedence.
+/// The `--config` file is listed even if it does not exist.
+pub(super) fn config_files(
+    repo_path: Option<&Path>,
+    sources: &ConfigSources,
+) -> Vec<(ConfigOrigin, PathBuf)> {
+    let mut files = vec![];
+
+    if let Some(path) = sources.user_config.as_ref().filter(|path| path.exists()) {
+        files.push((ConfigOrigin::User(path.clone()), path.clone()));
+    }
+
+    if let Some(repo_path) = repo_path {
+        for file in REPOSITORY_CONFIG_FILES.iter().rev() {
+            let path = repo_path.join(file);
+            if path.exists() {
+                files.push((ConfigOrigin::Repository(path.clone()), path));
+            }
+        }
+    }
+
+    if let Some(path) = &sources.config_path {
+        files.push((ConfigOrigin::Custom(path.clone()), path.clone()));
+    }
+
+    files
+}
+
+/// Find the repository settings, either in a dedicated config file or in a manifest section.
+/// When several sources are found they must hold the same settings, the one with the
+/// highest precedence is then used.
+fn repository_layer(repo_path: &Path) -> Result<Option<ConfigLayer>, ConfigError> {
+    let mut candidates = vec![];
+    for file in REPOSITORY_CONFIG_FILES {
+        let path = repo_path.join(file);
+        if path.exists() {
+            candidates.push(file_layer(ConfigOrigin::Repository(path.clone()), &path)?);
+        }
+    }
+
+    candidates.extend(manifest_layers(repo_path)?);
+
+    if candidates.len() > 1 {
+        let settings = candidates
+            .iter()
+            .map(|layer| layer.config.clone().try_deserialize::<Settings>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if settings.iter().any(|other| *other != settings[0]) {
+            let origins = candidates
+                .iter()
+                .map(|layer| layer.origin.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            return Err(ConfigError::Message(format!(
+                "conflicting cog configurations found: {origins}, keep only one"
+            )));
+        }
+    }
+
+    Ok(candidates.into_iter().next())
+}
+
+fn env_layers(env: &[(String, String)]) -> Result<Vec<ConfigLayer>, ConfigError> {
+    let mut layers = vec![];
+    for key in ENV_KEYS {
+        let var = format!("COG_{}", key.replace('.', "_").to_uppercase());
+        let value = match env.iter().find(|(name, _)| *name == var) {
+            Some((_, value)) => value.as_str(),
+            None => continue,
+        };
+
+        let builder = if ENV_LIST_KEYS.contains(key) {
+            let values: Vec<String> = value
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+                .collect();
+            Config::builder().set_override(*key, values)?
+        } else {
+            Config::builder().set_override(*key, value)?
+        };
+
+        layers.push(ConfigLayer {
+            origin: ConfigOrigin::Environment(var),
+            config: builder.build()?,
+        });
+    }
+
+    Ok(layers)
+}
+
+/// Configuration layers by increasing precedence: user config, repository config,
+/// `--config` file then environment variables.
+fn layers(
+    repo_path: Option<&Path>,
+    sources: &ConfigSources,
+) -> Result<Vec<ConfigLayer>, ConfigError> {
+    let mut layers = vec![];
+
+    if let Some(path) = sources.user_config.as_ref().filter(|path| path.exists()) {
+        layers.push(file_layer(ConfigOrigin::User(path.clone()), path)?);
+    }
+
+    if let Some(repo_path) = repo_path {
+        layers.extend(repository_layer(repo_path)?);
+    }
+
+    if let Some(path) = &sources.config_path {
+        layers.push(file_layer(ConfigOrigin::Custom(path.clone()), path)?);
+    }
+
+    layers.extend(env_layers(&sources.env)?);
+
+    Ok(layers)
+}
+
+impl Settings {
+    /// Merge all configuration layers, fails only if a layer is malformed
+    pub(crate) fn load(
+        repo_path: Option<&Path>,
+        sources: &ConfigSources,
+    ) -> Result<Self, SettingError> {
+        let mut builder = Config::builder();
+        for layer in layers(repo_path, sources)? {
+            builder = builder.add_source(layer.config);
+        }
+
+        builder
+            .build()?
+            .try_deserialize()
+            .map_err(SettingError::from)
+    }
+
+    /// Every value of the merged settings along with the layer it comes from
+    pub(crate) fn entries(
+        repo_path: Option<&Path>,
+        sources: &ConfigSources,
+    ) -> Result<Vec<ConfigEntry>, SettingError> {
+        let layers = layers(repo_path, sources)?;
+        let settings = Settings::load(repo_path, sources)?;
+        let settings = toml::Value::try_from(&settings)
+            .map_err(|err| SettingError::from(ConfigError::Foreign(Box::new(err))))?;
+
+        let layer_keys: Vec<(ConfigOrigin, Vec<String>)> = layers
+            .into_iter()
+            .map(|layer| {
+                let mut keys = vec![];
+                if let Ok(values) = layer.config.collect() {
+                    flatten_config_keys("", &values, &mut keys);
+                }
+                (layer.origin, keys)
+            })
+            .collect();
+
+        let mut values = BTreeMap::new();
+        flatten_toml("", &settings, &mut values);
+
+        Ok(values
+            .into_iter()
+            .map(|(key, value)| {
+                let origin = layer_keys
+                    .iter()
+                    .rev()
+                    .find(|(_, keys)| keys.contains(&key))
+                    .map(|(origin, _)| origin.clone())
+                    .unwrap_or(ConfigOrigin::Default);
+
+                ConfigEntry { key, value, origin }
+            })
+            .collect())
+    }
+}
+
+fn join_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{prefix}.{key}")
+    }
+}
+
+fn flatten_config_keys(prefix: &str, values: &Map<String, Value>, keys: &mut Vec<String>) {
+    for (key, value) in values {
+        let key = join_key(prefix, key);
+        match &value.kind {
+            ValueKind::Table(table) if !table.is_empty() => flatten_config_keys(&key, table, keys),
+            _ => keys.push(key),
+        }
+    }
+}
+
+fn flatten_toml(prefix: &str, value: &toml::Value, values: &mut BTreeMap<String, toml::Value>) {
+    match value {
+        toml::Value::Table(table) if !table.is_empty() => {
+            for (key, value) in table {
+                flatten_toml(&join_key(prefix, key), value, values);
+            }
+        }
+        // Empty tables carry no setting
+        toml::Value::Table(_) => {}
+        value => {
+            values.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+/// Render a value as inline toml
+fn inline(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(string) => {
+            serde_json::to_string(string).expect("a string is valid json")
+        }
+        toml::Value::Array(values) => {
+            format!(
+                "[{}]",
+                values.iter().map(inline).collect::<Vec<_>>().join(", ")
+            )
+        }
+        toml::Value::Table(table) => {
+            let values: Vec<_> = table
+                .iter()
+                .map(|(key, value)| format!("{} = {}", key, inline(value)))
+                .collect();
+            format!("{{ {} }}", values.join(", "))
+        }
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::settings::source::{ConfigOrigin, ConfigSources};
+    use crate::settings::Settings;
+
+    use anyhow::Result;
+    use sealed_test::prelude::*;
+    use speculoos::prelude::*;
+
+    #[sealed_test(env = [("XDG_CONFIG_HOME", "user_config"), ("COG_NO_USER_CONFIG", "")])]
+    fn repository_config_overrides_user_config() -> Result<()> {
+        // Arrange
+        fs::create_dir_all("user_config/cog")?;
+        fs::write(
+            "user_config/cog/cog.toml",
+            "tag_prefix = \"user-\"\nignore_merge_commits = true\n",
+        )?;
+        fs::write("cog.toml", "tag_prefix = \"v\"\n")?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let settings = Settings::load(Some(&repo_path), &ConfigSources::from_env(None))?;
+
+        // Assert
+        assert_that!(settings.tag_prefix).is_equal_to(Some("v".to_string()));
+        assert_that!(settings.ignore_merge_commits).is_true();
+        Ok(())
+    }
+
+    #[sealed_test(env = [("XDG_CONFIG_HOME", "user_config"), ("COG_NO_USER_CONFIG", "1")])]
+    fn no_user_config_skips_user_config() -> Result<()> {
+        // Arrange
+        fs::create_dir_all("user_config/cog")?;
+        fs::write("user_config/cog/cog.toml", "tag_prefix = \"user-\"\n")?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let settings = Settings::load(Some(&repo_path), &ConfigSources::from_env(None))?;
+
+        // Assert
+        assert_that!(settings.tag_prefix).is_none();
+        Ok(())
+    }
+
+    #[sealed_test(env = [
+        ("XDG_CONFIG_HOME", "user_config"),
+        ("COG_NO_USER_CONFIG", ""),
+        ("COG_TAG_PREFIX", "env-"),
+        ("COG_BRANCH_WHITELIST", "main, release/**"),
+        ("COG_VERSION", "1.0.0"),
+        ("COG_CHANGELOG", "## 1.0.0"),
+    ])]
+    fn environment_overrides_config_files() -> Result<()> {
+        // Arrange
+        fs::write("cog.toml", "tag_prefix = \"v\"\n")?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let settings = Settings::load(Some(&repo_path), &ConfigSources::from_env(None))?;
+
+        // Assert
+        assert_that!(settings.tag_prefix).is_equal_to(Some("env-".to_string()));
+        assert_that!(settings.branch_whitelist)
+            .is_equal_to(vec!["main".to_string(), "release/**".to_string()]);
+        Ok(())
+    }
+
+    #[sealed_test(env = [("XDG_CONFIG_HOME", "user_config"), ("COG_NO_USER_CONFIG", "")])]
+    fn load_from_alternative_config_file() -> Result<()> {
+        // Arrange
+        fs::create_dir_all(".config")?;
+        fs::write(".config/cog.toml", "tag_prefix = \"v\"\n")?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let settings = Settings::load(Some(&repo_path), &ConfigSources::default())?;
+
+        // Assert
+        assert_that!(settings.tag_prefix).is_equal_to(Some("v".to_string()));
+        Ok(())
+    }
+
+    #[sealed_test(env = [("XDG_CONFIG_HOME", "user_config"), ("COG_NO_USER_CONFIG", "")])]
+    fn load_from_cargo_metadata() -> Result<()> {
+        // Arrange
+        fs::write(
+            "Cargo.toml",
+            "[workspace]\nmembers = []\n\n[workspace.metadata.cog]\ntag_prefix = \"v\"\n",
+        )?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let settings = Settings::load(Some(&repo_path), &ConfigSources::default())?;
+
+        // Assert
+        assert_that!(settings.tag_prefix).is_equal_to(Some("v".to_string()));
+        Ok(())
+    }
+
+    #[sealed_test(env = [("XDG_CONFIG_HOME", "user_config"), ("COG_NO_USER_CONFIG", "")])]
+    fn load_from_package_json() -> Result<()> {
+        // Arrange
+        fs::write(
+            "package.json",
+            r#"{ "name": "app", "cog": { "tag_prefix": "v", "changelog": { "path": "CHANGES.md" } } }"#,
+        )?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let settings = Settings::load(Some(&repo_path), &ConfigSources::default())?;
+
+        // Assert
+        assert_that!(settings.tag_prefix).is_equal_to(Some("v".to_string()));
+        assert_that!(settings.changelog.path).is_equal_to(PathBuf::from("CHANGES.md"));
+        Ok(())
+    }
+
+    #[sealed_test(env = [("XDG_CONFIG_HOME", "user_config"), ("COG_NO_USER_CONFIG", "")])]
+    fn malformed_manifests_without_cog_settings_are_skipped() -> Result<()> {
+        // Arrange
+        fs::write("cog.toml", "tag_prefix = \"v\"\n")?;
+        fs::write("Cargo.toml", "[package\nname = \"app\"\n")?;
+        fs::write("package.json", r#"{ "name": "app", }"#)?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let settings = Settings::load(Some(&repo_path), &ConfigSources::default());
+
+        // Assert
+        assert_that!(settings)
+            .is_ok()
+            .map(|settings| &settings.tag_prefix)
+            .is_equal_to(Some("v".to_string()));
+        Ok(())
+    }
+
+    #[sealed_test(env = [("XDG_CONFIG_HOME", "user_config"), ("COG_NO_USER_CONFIG", "")])]
+    fn malformed_manifest_with_cog_settings_is_err() -> Result<()> {
+        // Arrange
+        fs::write(
+            "Cargo.toml",
+            "[package\nname = \"app\"\n\n[package.metadata.cog]\ntag_prefix = \"v\"\n",
+        )?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let settings = Settings::load(Some(&repo_path), &ConfigSources::default());
+
+        // Assert
+        assert_that!(settings)
+            .is_err()
+            .matches(|err| err.to_string().contains("Cargo.toml"));
+        Ok(())
+    }
+
+    #[sealed_test(env = [("XDG_CONFIG_HOME", "user_config"), ("COG_NO_USER_CONFIG", "")])]
+    fn identical_config_sources_do_not_conflict() -> Result<()> {
+        // Arrange
+        fs::write("cog.toml", "tag_prefix = \"v\"\n")?;
+        fs::write("package.json", r#"{ "cog": { "tag_prefix": "v" } }"#)?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let settings = Settings::load(Some(&repo_path), &ConfigSources::default());
+
+        // Assert
+        assert_that!(settings)
+            .is_ok()
+            .map(|settings| &settings.tag_prefix)
+            .is_equal_to(Some("v".to_string()));
+        Ok(())
+    }
+
+    #[sealed_test(env = [("XDG_CONFIG_HOME", "user_config"), ("COG_NO_USER_CONFIG", "")])]
+    fn conflicting_config_sources_are_err() -> Result<()> {
+        // Arrange
+        fs::write("cog.toml", "tag_prefix = \"v\"\n")?;
+        fs::write(".cog.toml", "tag_prefix = \"release-\"\n")?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let settings = Settings::load(Some(&repo_path), &ConfigSources::default());
+
+        // Assert
+        assert_that!(settings).is_err().matches(|err| {
+            err.to_string()
+                .contains("conflicting cog configurations found")
+        });
+        Ok(())
+    }
+
+    #[sealed_test(env = [("XDG_CONFIG_HOME", "user_config"), ("COG_NO_USER_CONFIG", ""), ("COG_FROM_LATEST_TAG", "true")])]
+    fn entries_report_origin() -> Result<()> {
+        // Arrange
+        fs::create_dir_all("user_config/cog")?;
+        fs::write("user_config/cog/cog.toml", "ignore_merge_commits = true\n")?;
+        fs::write("cog.toml", "[changelog]\npath = \"CHANGES.md\"\n")?;
+        let repo_path = std::env::current_dir()?;
+
+        // Act
+        let entries = Settings::entries(Some(&repo_path), &ConfigSources::from_env(None))?;
+
+        // Assert
+        let origin = |key: &str| {
+            entries
+                .iter()
+                .find(|entry| entry.key == key)
+                .map(|entry| entry.origin.clone())
+        };
+
+        assert_that!(origin("ignore_merge_commits")).is_equal_to(Some(ConfigOrigin::User(
+            PathBuf::from("user_config/cog/cog.toml"),
+        )));
+        assert_that!(origin("changelog.path"))
+            .is_equal_to(Some(ConfigOrigin::Repository(repo_path.join("cog.toml"))));
+        assert_that!(origin("from_latest_tag")).is_equal_to(Some(ConfigOrigin::Environment(
+            "COG_FROM_LATEST_TAG".to_string(),
+        )));
+        assert_that!(origin("parse_squash_commits")).is_equal_to(Some(ConfigOrigin::Default));
+        Ok(())
+    }
+}
@@ -4,8 +4,10 @@ use crate::helpers::*;
 
 use anyhow::Result;
 use assert_cmd::prelude::*;
-use cocogitto::settings::Settings;
+use cmd_lib::run_fun;
+use cocogitto::settings::{HookConfig, Settings};
 use indoc::indoc;
+use predicates::prelude::predicate;
 use sealed_test::prelude::*;
 use speculoos::prelude::*;
 use std::path::Path;
@@ -74,6 +76,59 @@ fn auto_bump_dry_run_from_latest_tag() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+fn auto_bump_dry_run_prints_hooks() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        r#"pre_bump_hooks = ["touch {{version}}"]
+post_bump_hooks = ["echo {{latest}} {{version+1minor-dev}}"]"#,
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .arg("--dry-run")
+        // Assert
+        .assert()
+        .success()
+        .stdout("1.1.0\n")
+        .stderr(predicate::str::contains("[touch 1.1.0]"))
+        .stderr(predicate::str::contains("[echo 1.0.0 1.2.0-dev]"));
+
+    assert_that!(Path::new("1.1.0")).does_not_exist();
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_dry_run_fails_on_invalid_hook() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(r#"pre_bump_hooks = ["touch {{versoin}}"]"#, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .arg("--dry-run")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown variable `{{versoin}}`"));
+
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
 #[sealed_test]
 fn auto_bump_major_from_latest_tag() -> Result<()> {
     git_init()?;
@@ -260,6 +315,167 @@ fn bump_with_hook() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_hooks_receive_env() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        r#"pre_bump_hooks = ["echo \"$COG_PREVIOUS_VERSION $COG_VERSION $COG_INCREMENT\" > env.txt"]"#,
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success();
+
+    let env = std::fs::read_to_string("env.txt")?;
+    assert_that!(env.as_str()).is_equal_to("1.0.0 1.1.0 minor\n");
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_with_hook_table() -> Result<()> {
+    // Arrange
+    git_init()?;
+    let config = indoc! {
+        r#"pre_bump_hooks = [
+            "touch {{version}}",
+            { command = "touch $FILE", shell = "bash", timeout = 10, env = { FILE = "from-table" } },
+            { command = "exit 1", retries = 1, allow_failure = true },
+        ]
+        "#
+    };
+    git_add(config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--major")
+        // Assert
+        .assert()
+        .success();
+
+    assert_that!(Path::new("2.0.0")).exists();
+    assert_that!(Path::new("from-table")).exists();
+    assert_tag_exists("2.0.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_with_conditional_and_parallel_hooks() -> Result<()> {
+    // Arrange
+    git_init()?;
+    // Each parallel hook waits for the other one to start, they only succeed when run concurrently
+    let config = indoc! {
+        r#"post_bump_hooks = [
+            { command = "touch a && for i in $(seq 50); do [ -f b ] && exit 0; sleep 0.1; done; exit 1", parallel = "publish" },
+            { command = "touch b && for i in $(seq 50); do [ -f a ] && exit 0; sleep 0.1; done; exit 1", parallel = "publish" },
+            { command = "touch major", when = "major" },
+            { command = "touch minor", when = "minor" },
+            { command = "touch other-branch", when_branch = "release/*" },
+        ]
+        "#
+    };
+    git_add(config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success();
+
+    assert_that!(Path::new("a")).exists();
+    assert_that!(Path::new("b")).exists();
+    assert_that!(Path::new("minor")).exists();
+    assert_that!(Path::new("major")).does_not_exist();
+    assert_that!(Path::new("other-branch")).does_not_exist();
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_runs_hook_stages_in_order() -> Result<()> {
+    // Arrange
+    git_init()?;
+    let config = indoc! {
+        r#"pre_changelog_hooks = ["echo pre-changelog >> stages"]
+        post_changelog_hooks = ["test -f CHANGELOG.md && echo post-changelog >> stages"]
+        pre_bump_hooks = ["echo pre-bump >> stages"]
+        pre_commit_hooks = ["echo pre-commit >> stages"]
+        pre_tag_hooks = ["git log -1 --format=%s > pre-tag"]
+        post_bump_hooks = ["echo post-bump >> stages"]
+        "#
+    };
+    git_add(config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success();
+
+    let stages = std::fs::read_to_string("stages")?;
+    assert_that!(stages.as_str())
+        .is_equal_to("pre-changelog\npost-changelog\npre-bump\npre-commit\npost-bump\n");
+    let pre_tag = std::fs::read_to_string("pre-tag")?;
+    assert_that!(pre_tag.as_str()).is_equal_to("chore(version): 1.1.0\n");
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_runs_on_failure_hooks() -> Result<()> {
+    // Arrange
+    git_init()?;
+    let config = indoc! {
+        r#"pre_bump_hooks = ["exit 1"]
+        on_failure_hooks = ["echo on-failure {{version}}"]
+        "#
+    };
+    git_add(config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("on-failure 1.1.0"));
+
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
 #[sealed_test]
 #[cfg(target_os = "linux")]
 fn bump_with_profile_hook() -> Result<()> {
@@ -315,6 +531,35 @@ fn monorepo_dry_run() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn monorepo_global_hooks_receive_bumped_packages() -> Result<()> {
+    // Arrange
+    let mut settings = Settings {
+        pre_bump_hooks: vec![HookConfig::Command(
+            "echo {{packages}} > packages.txt && cp $COG_PACKAGES_FILE packages.json".to_string(),
+        )],
+        ..Default::default()
+    };
+    init_monorepo(&mut settings)?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success();
+
+    let packages = std::fs::read_to_string("packages.txt")?;
+    assert_that!(packages.as_str()).is_equal_to("one\n");
+    let packages_json = std::fs::read_to_string("packages.json")?;
+    assert_that!(packages_json.as_str()).contains(r#""tag": "one-0.1.0""#);
+    assert_that!(packages_json.as_str()).contains(r#""previous_version": null"#);
+    assert_tag_exists("0.1.0")?;
+    Ok(())
+}
+
 #[sealed_test]
 fn package_dry_run() -> Result<()> {
     init_monorepo(&mut Settings::default())?;
@@ -333,3 +578,163 @@ fn package_dry_run() -> Result<()> {
     assert_tag_does_not_exist("1.1.0")?;
     Ok(())
 }
+
+#[sealed_test]
+fn bump_with_custom_changelog_header_footer_and_separator() -> Result<()> {
+    // Arrange
+    git_init()?;
+    let config = indoc! {
+        r##"[changelog]
+        remote = "github.com"
+        owner = "cocogitto"
+        repository = "cocogitto"
+        header = "# Changes of [{{ owner }}]({{ repository_url }})"
+        footer = "Generated by cog"
+        separator = "<!-- next -->"
+        "##
+    };
+    git_add(config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--minor")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = std::fs::read_to_string("CHANGELOG.md")?;
+    assert_that!(changelog.as_str()).starts_with(indoc! {
+        "# Changes of [cocogitto](https://github.com/cocogitto/cocogitto)
+
+        <!-- next -->
+        ## 1.1.0 - "
+    });
+    assert_that!(changelog.as_str()).ends_with("<!-- next -->\n\nGenerated by cog");
+    assert_that!(changelog.as_str()).does_not_contain("- - -");
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_inserts_release_below_unreleased_section() -> Result<()> {
+    // Arrange
+    git_init()?;
+    let changelog = indoc! {
+        "# Changelog
+
+        ## [Unreleased]
+
+        ## [1.0.0] - 2022-01-01
+        ### Added
+        - a feature
+
+        [1.0.0]: https://github.com/cocogitto/cocogitto/releases/tag/1.0.0
+        "
+    };
+    git_add(changelog, "CHANGELOG.md")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--minor")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = std::fs::read_to_string("CHANGELOG.md")?;
+    assert_that!(changelog.as_str()).starts_with("# Changelog\n\n## [Unreleased]\n\n## 1.1.0 - ");
+    assert_that!(changelog.as_str()).contains("- feature - (");
+    assert_that!(changelog.as_str()).contains(indoc! {
+        "
+
+        ## [1.0.0] - 2022-01-01
+        ### Added
+        - a feature
+
+        [1.0.0]: https://github.com/cocogitto/cocogitto/releases/tag/1.0.0
+        "
+    });
+    assert_that!(changelog.as_str()).does_not_contain("- - -");
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_fails_without_changelog_separator() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("# Changelog\n", "CHANGELOG.md")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--minor")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "cannot find separator '- - -' or an '## [Unreleased]' section in",
+        ))
+        .stderr(predicate::str::contains("CHANGELOG.md"));
+
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_resets_version_commit_on_pre_tag_hook_failure() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(r#"pre_tag_hooks = ["exit 1"]"#, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .failure();
+
+    assert_tag_does_not_exist("1.1.0")?;
+    assert_that!(run_fun!(git log -1 --format=%s)?).is_equal_to("feat: feature".to_string());
+    assert_that!(run_fun!(git stash list)?).contains("cog_bump_1.1.0");
+    assert_that!(run_fun!(git status --porcelain)?).is_empty();
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn monorepo_bump_creates_no_tag_on_package_pre_tag_hook_failure() -> Result<()> {
+    // Arrange
+    let mut settings = Settings {
+        pre_package_tag_hooks: vec![HookConfig::Command("exit 1".to_string())],
+        ..Default::default()
+    };
+    init_monorepo(&mut settings)?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .failure();
+
+    assert_tag_does_not_exist("one-0.1.0")?;
+    assert_tag_does_not_exist("0.1.0")?;
+    assert_that!(run_fun!(git log -1 --format=%s)?)
+        .is_equal_to("feat: package one feature".to_string());
+    Ok(())
+}
@@ -1,10 +1,12 @@
 use anyhow::Result;
 use assert_cmd::Command;
 use chrono::Utc;
-use cmd_lib::run_cmd;
+use cmd_lib::{run_cmd, run_fun};
 use indoc::{formatdoc, indoc};
+use predicates::prelude::*;
 use pretty_assertions::assert_eq;
 use sealed_test::prelude::*;
+use speculoos::prelude::*;
 use std::fs;
 use std::path::PathBuf;
 
@@ -437,3 +439,388 @@ fn get_changelog_whith_custom_template() -> Result<()> {
     );
     Ok(())
 }
+
+#[sealed_test]
+fn get_changelog_with_remote_inferred_from_origin() -> Result<()> {
+    // Arrange
+    git_init()?;
+    run_cmd!(git remote add origin git@gitlab.com:group/subgroup/test.git;)?;
+    git_commit("chore: init")?;
+    let commit = git_commit("feat: feature 1")?;
+
+    // Act
+    let changelog = Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("-t")
+        .arg("remote")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = changelog.get_output();
+    let changelog = String::from_utf8_lossy(&changelog.stdout);
+
+    assert!(changelog.contains(&format!(
+        "([{}](https://gitlab.com/group/subgroup/test/-/commit/{commit}))",
+        &commit[0..7]
+    )));
+    Ok(())
+}
+
+#[sealed_test]
+fn write_full_changelog_keeps_header_and_footer() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("feat: first feature")?;
+    git_tag("1.0.0")?;
+    git_commit("fix: a bug fix")?;
+    git_tag("1.0.1")?;
+    git_commit("feat: unreleased feature")?;
+    fs::write(
+        "CHANGELOG.md",
+        "# My project\n\nHand written intro\n- - -\n## 0.1.0 - outdated\n- - -\n\nMy footer",
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--write")
+        .arg("--full")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = fs::read_to_string("CHANGELOG.md")?;
+    let today = Utc::now().date_naive();
+
+    assert_eq!(
+        changelog,
+        formatdoc!(
+            "# My project
+
+            Hand written intro
+            - - -
+            ## 1.0.1 - {today}
+            #### Bug Fixes
+            - a bug fix - ({fix}) - Tom
+
+            - - -
+
+            ## 1.0.0 - {today}
+            #### Features
+            - first feature - ({feat}) - Tom
+
+            - - -
+
+            My footer",
+            fix = &run_fun!(git rev-parse --short=7 1.0.1)?,
+            feat = &run_fun!(git rev-parse --short=7 1.0.0)?,
+        )
+    );
+    Ok(())
+}
+
+#[sealed_test]
+fn write_full_changelog_dates_releases_from_their_tag() -> Result<()> {
+    // Arrange
+    git_init()?;
+    run_cmd!(
+        GIT_COMMITTER_DATE="2019-12-01T12:00:00Z" git commit --allow-empty -q -m "chore: init";
+        GIT_COMMITTER_DATE="2020-01-01T12:00:00Z" git commit --allow-empty -q -m "feat: first feature";
+        git tag 0.1.0;
+        GIT_COMMITTER_DATE="2020-02-01T12:00:00Z" git commit --allow-empty -q -m "fix: a bug fix";
+        git tag 0.1.1;
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--write")
+        .arg("--full")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = fs::read_to_string("CHANGELOG.md")?;
+    assert_that!(changelog).contains("## 0.1.1 - 2020-02-01");
+    assert_that!(changelog).contains("## 0.1.0 - 2020-01-01");
+    Ok(())
+}
+
+#[sealed_test]
+fn full_changelog_prints_the_written_releases() -> Result<()> {
+    // Arrange
+    git_init()?;
+    run_cmd!(
+        GIT_COMMITTER_DATE="2019-12-01T12:00:00Z" git commit --allow-empty -q -m "chore: init";
+        GIT_COMMITTER_DATE="2020-01-01T12:00:00Z" git commit --allow-empty -q -m "feat: first feature";
+        git tag 1.0.0;
+        GIT_COMMITTER_DATE="2020-02-01T12:00:00Z" git commit --allow-empty -q -m "fix: a bug fix";
+        git tag 1.0.1;
+    )?;
+    fs::write("CHANGELOG.md", "# My project\n- - -\n- - -\n")?;
+
+    // Act
+    let printed = Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--full")
+        .assert()
+        .success();
+
+    Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--write")
+        .arg("--full")
+        .assert()
+        .success();
+
+    // Assert
+    let printed = String::from_utf8(printed.get_output().stdout.clone())?;
+    let changelog = fs::read_to_string("CHANGELOG.md")?;
+    assert_that!(changelog).contains(printed.trim_end());
+    Ok(())
+}
+
+#[sealed_test]
+fn write_full_changelog_uses_configured_separator() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("[changelog]\nseparator = \"<!-- next -->\"\n", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_commit("feat: first feature")?;
+    git_tag("1.0.0")?;
+    git_commit("fix: a bug fix")?;
+    git_tag("1.0.1")?;
+    fs::write(
+        "CHANGELOG.md",
+        "# My project\n<!-- next -->\n<!-- next -->\n",
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--write")
+        .arg("--full")
+        .assert()
+        .success();
+
+    Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--write")
+        .arg("--at")
+        .arg("1.0.0")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = fs::read_to_string("CHANGELOG.md")?;
+    assert_that!(changelog).does_not_contain("- - -");
+    assert_that!(changelog.matches("<!-- next -->").count()).is_equal_to(3);
+    assert_that!(changelog).contains("first feature");
+    Ok(())
+}
+
+#[sealed_test]
+fn write_changelog_follows_keep_a_changelog_layout() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("feat: first feature")?;
+    git_tag("1.0.0")?;
+    git_commit("fix: a bug fix")?;
+    git_tag("1.0.1")?;
+    fs::write(
+        "CHANGELOG.md",
+        "# Changelog\n\n## [Unreleased]\n- upcoming change\n",
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--write")
+        .arg("--full")
+        .assert()
+        .success();
+
+    Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--write")
+        .arg("--at")
+        .arg("1.0.0")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = fs::read_to_string("CHANGELOG.md")?;
+    let today = Utc::now().date_naive();
+    assert_eq!(
+        changelog,
+        formatdoc!(
+            "# Changelog
+
+            ## [Unreleased]
+            - upcoming change
+
+            ## 1.0.1 - {today}
+            #### Bug Fixes
+            - a bug fix - ({fix}) - Tom
+
+            ## 1.0.0 - {today}
+            #### Features
+            - first feature - ({feat}) - Tom
+            ",
+            fix = &run_fun!(git rev-parse --short=7 1.0.1)?,
+            feat = &run_fun!(git rev-parse --short=7 1.0.0)?,
+        )
+    );
+    Ok(())
+}
+
+#[sealed_test]
+fn write_full_changelog_regenerates_package_changelogs() -> Result<()> {
+    // Arrange
+    init_monorepo(&mut Settings::default())?;
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+    fs::remove_file("CHANGELOG.md")?;
+    fs::remove_file("one/CHANGELOG.md")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--write")
+        .arg("--full")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = fs::read_to_string("CHANGELOG.md")?;
+    let package_changelog = fs::read_to_string("one/CHANGELOG.md")?;
+    assert_that!(changelog).contains("## 0.1.0");
+    assert_that!(changelog).contains("one-0.1.0");
+    assert_that!(package_changelog).contains("## one-0.1.0");
+    assert_that!(package_changelog).contains("package one feature");
+    Ok(())
+}
+
+#[sealed_test]
+fn write_changelog_at_rewrites_a_single_release() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("feat: first feature")?;
+    git_tag("1.0.0")?;
+    git_commit("fix: a bug fix")?;
+    git_tag("1.0.1")?;
+    let today = Utc::now().date_naive();
+    fs::write(
+        "CHANGELOG.md",
+        formatdoc!(
+            "# My project
+            - - -
+            ## 1.0.1 - {today}
+            - hand edited entry
+
+            - - -
+
+            ## 1.0.0 - outdated
+            - stale entry
+
+            - - -
+
+            My footer"
+        ),
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--write")
+        .arg("--at")
+        .arg("1.0.0")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = fs::read_to_string("CHANGELOG.md")?;
+
+    assert_eq!(
+        changelog,
+        formatdoc!(
+            "# My project
+            - - -
+            ## 1.0.1 - {today}
+            - hand edited entry
+
+            - - -
+
+            ## 1.0.0 - {today}
+            #### Features
+            - first feature - ({feat}) - Tom
+
+            - - -
+
+            My footer",
+            feat = &run_fun!(git rev-parse --short=7 1.0.0)?,
+        )
+    );
+    Ok(())
+}
+
+#[sealed_test]
+fn write_changelog_at_keeps_the_release_date() -> Result<()> {
+    // Arrange
+    git_init()?;
+    run_cmd!(
+        GIT_COMMITTER_DATE="2019-12-01T12:00:00Z" git commit --allow-empty -q -m "chore: init";
+        GIT_COMMITTER_DATE="2020-01-01T12:00:00Z" git commit --allow-empty -q -m "feat: first feature";
+        git tag 0.1.0;
+    )?;
+    fs::write(
+        "CHANGELOG.md",
+        "# My project\n- - -\n## 0.1.0 - 2020-01-01\n- typo in entry\n\n- - -\n",
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--write")
+        .arg("--at")
+        .arg("0.1.0")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = fs::read_to_string("CHANGELOG.md")?;
+    assert_that!(changelog).contains("## 0.1.0 - 2020-01-01\n#### Features");
+    assert_that!(changelog).does_not_contain("typo in entry");
+    Ok(())
+}
+
+#[sealed_test]
+fn write_changelog_at_fails_without_release_section() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("feat: first feature")?;
+    git_tag("1.0.0")?;
+    fs::write("CHANGELOG.md", "# My project\n- - -\n- - -\n")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("--write")
+        .arg("--at")
+        .arg("1.0.0")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "cannot find a release section for 1.0.0",
+        ));
+    Ok(())
+}
@@ -2,8 +2,10 @@ use crate::helpers::*;
 
 use anyhow::Result;
 use assert_cmd::Command;
+use cmd_lib::run_cmd;
 use predicates::prelude::predicate;
 use sealed_test::prelude::*;
+use speculoos::prelude::*;
 
 #[sealed_test]
 fn cog_check_ok() -> Result<()> {
@@ -83,3 +85,170 @@ fn cog_check_from_latest_tag_failure() -> Result<()> {
         .stderr(predicate::str::contains("Found 1 non compliant commits"));
     Ok(())
 }
+
+#[sealed_test]
+fn cog_check_range_ok() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("toto: errored commit")?;
+    let from = git_commit("feat: feature")?;
+    git_commit("fix: bug fix")?;
+    let to = git_commit("docs: readme")?;
+    git_commit("toto: africa")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        .arg(format!("{from}..{to}"))
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No errored commits"));
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_range_failure() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    let from = git_commit("toto: errored commit")?;
+    git_commit("feat: feature")?;
+    git_commit("toto: africa")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        .arg(format!("{from}..HEAD"))
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Found 1 non compliant commits"));
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_since_ref_failure() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("toto: errored commit")?;
+    let since = git_commit("feat: feature")?;
+    git_commit("toto: africa")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        .arg("--since-ref")
+        .arg(&since)
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(format!(
+            "Found 1 non compliant commits in {}..",
+            &since[0..6]
+        )));
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_unknown_revision_fails() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        .arg("--since-ref")
+        .arg("origin/main")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("revision `origin/main` not found"));
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_invalid_range_fails() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        .arg("HEAD")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid commit range pattern 'HEAD', expected `<from>..<to>`",
+        ));
+    Ok(())
+}
+
+#[sealed_test]
+fn pre_push_hook_checks_root_commit_of_new_branch() -> Result<()> {
+    // Arrange
+    git_init()?;
+    run_cmd!(
+        git commit --allow-empty -q -m "not a conventional commit";
+        git commit --allow-empty -q -m "feat: feature";
+        git tag 1.0.0;
+        git commit --allow-empty -q -m "fix: bug fix";
+        git init -q --bare remote.git;
+        git remote add origin remote.git;
+    )?;
+    Command::cargo_bin("cog")?
+        .arg("install-hook")
+        .arg("pre-push")
+        .assert()
+        .success();
+
+    // Act
+    let status = std::process::Command::new("git")
+        .args(["push", "-q", "origin", "master"])
+        .env("PATH", path_with_cog()?)
+        .status()?;
+
+    // Assert
+    assert_that!(status.success()).is_false();
+    Ok(())
+}
+
+#[sealed_test]
+fn pre_push_hook_accepts_valid_new_branch() -> Result<()> {
+    // Arrange
+    git_init()?;
+    run_cmd!(
+        git commit --allow-empty -q -m "chore: init";
+        git commit --allow-empty -q -m "feat: feature";
+        git init -q --bare remote.git;
+        git remote add origin remote.git;
+    )?;
+    Command::cargo_bin("cog")?
+        .arg("install-hook")
+        .arg("pre-push")
+        .assert()
+        .success();
+
+    // Act
+    let status = std::process::Command::new("git")
+        .args(["push", "-q", "origin", "master"])
+        .env("PATH", path_with_cog()?)
+        .status()?;
+
+    // Assert
+    assert_that!(status.success()).is_true();
+    Ok(())
+}
+
+/// The `PATH` of the current process, with the cog binary under test first
+fn path_with_cog() -> Result<std::ffi::OsString> {
+    let cog = assert_cmd::cargo::cargo_bin("cog");
+    let cog_dir = cog.parent().expect("cog binary directory").to_path_buf();
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    let paths = std::iter::once(cog_dir).chain(std::env::split_paths(&path));
+    Ok(std::env::join_paths(paths)?)
+}
@@ -0,0 +1,133 @@
+use crate::helpers::*;
+
+use anyhow::Result;
+use assert_cmd::Command;
+use predicates::prelude::*;
+use sealed_test::prelude::*;
+use std::fs;
+
+#[sealed_test]
+fn config_show_prints_origin_of_each_value() -> Result<()> {
+    // Arrange
+    git_init()?;
+    fs::write("cog.toml", "tag_prefix = \"v\"\n")?;
+    fs::write("custom.toml", "ignore_merge_commits = true\n")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .env("XDG_CONFIG_HOME", "user_config")
+        .env_remove("COG_NO_USER_CONFIG")
+        .env("COG_FROM_LATEST_TAG", "true")
+        .env("COG_VERSION", "1.0.0")
+        .arg("--config")
+        .arg("custom.toml")
+        .arg("config")
+        .arg("show")
+        // Assert
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "tag_prefix = \"v\" # repository config",
+        ))
+        .stdout(predicate::str::contains(
+            "ignore_merge_commits = true # --config custom.toml",
+        ))
+        .stdout(predicate::str::contains(
+            "from_latest_tag = true # environment variable COG_FROM_LATEST_TAG",
+        ))
+        .stdout(predicate::str::contains(
+            "parse_squash_commits = false # default",
+        ));
+    Ok(())
+}
+
+#[sealed_test]
+fn config_file_overrides_repository_config() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("tag_prefix = \"v\"\n", "cog.toml")?;
+    git_add("tag_prefix = \"release-\"\n", "custom.toml")?;
+    git_commit("chore: init")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .env("XDG_CONFIG_HOME", "user_config")
+        .env_remove("COG_NO_USER_CONFIG")
+        .arg("bump")
+        .arg("--auto")
+        .arg("--config")
+        .arg("custom.toml")
+        // Assert
+        .assert()
+        .success();
+
+    assert_tag_exists("release-0.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn config_check_reports_problems() -> Result<()> {
+    // Arrange
+    git_init()?;
+    fs::write("cog.toml", "branch_whitelist = [\"[main\"]\n")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .env("XDG_CONFIG_HOME", "user_config")
+        .env_remove("COG_NO_USER_CONFIG")
+        .arg("config")
+        .arg("check")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "cog.toml:1:21: invalid branch_whitelist pattern `[main`",
+        ));
+    Ok(())
+}
+
+#[sealed_test]
+fn malformed_config_fails_commands() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    fs::write("cog.toml", "tag_prefixes = \"v\"\n")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .env("XDG_CONFIG_HOME", "user_config")
+        .env_remove("COG_NO_USER_CONFIG")
+        .arg("check")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown field `tag_prefixes`"))
+        .stderr(predicate::str::contains(
+            "Run `cog config check` for details",
+        ));
+    Ok(())
+}
+
+#[sealed_test]
+fn malformed_config_fails_before_parsing_arguments() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    fs::write("cog.toml", "tag_prefixes = \"v\"\n")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .env("XDG_CONFIG_HOME", "user_config")
+        .env_remove("COG_NO_USER_CONFIG")
+        .arg("bump")
+        .arg("--auto")
+        .arg("--package")
+        .arg("one")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown field `tag_prefixes`"))
+        .stderr(predicate::str::contains("invalid value").not());
+    Ok(())
+}
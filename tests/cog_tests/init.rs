@@ -1,13 +1,17 @@
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str::FromStr;
 
+use cocogitto::settings::Settings;
 use cocogitto::CONFIG_PATH;
 
 use crate::helpers::*;
 
 use anyhow::Result;
 use assert_cmd::prelude::*;
+use cmd_lib::run_cmd;
+use predicates::prelude::*;
 use sealed_test::prelude::*;
 use speculoos::prelude::*;
 
@@ -70,3 +74,66 @@ fn init_current_dir_with_no_arg() -> Result<()> {
         .success();
     Ok(())
 }
+
+#[sealed_test]
+fn init_with_packages_remote_and_hook() -> Result<()> {
+    // Arrange
+    git_init()?;
+    run_cmd!(
+        git remote add origin "https://github.com/cocogitto/cocogitto.git";
+        echo "[workspace]\nmembers = [\"crates/*\"]" > Cargo.toml;
+        mkdir -p crates/one;
+        echo "[package]\nname = \"one\"" > crates/one/Cargo.toml;
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("init")
+        .arg("--packages")
+        .arg("--remote")
+        .args(["--template", "remote"])
+        .args(["--install-hook", "commit-msg"])
+        // Assert
+        .assert()
+        .success();
+
+    let settings: Settings = toml::from_str(&fs::read_to_string(CONFIG_PATH)?)?;
+    assert_that!(settings.packages.get("one").map(|package| &package.path))
+        .is_equal_to(Some(&PathBuf::from("crates/one")));
+    assert_that!(settings.changelog.remote).is_equal_to(Some("github.com".to_string()));
+    assert_that!(settings.changelog.owner).is_equal_to(Some("cocogitto".to_string()));
+    assert_that!(settings.changelog.repository).is_equal_to(Some("cocogitto".to_string()));
+    assert_that!(settings.changelog.template).is_equal_to(Some("remote".to_string()));
+    assert_that!(Path::new(".git/hooks/commit-msg")).exists();
+    Ok(())
+}
+
+#[sealed_test]
+fn init_interactive() -> Result<()> {
+    // Arrange
+    git_init()?;
+    run_cmd!(
+        git remote add origin git@github.com:cocogitto/cocogitto.git;
+        echo "{ \"workspaces\": [\"packages/*\"] }" > package.json;
+        mkdir -p packages/web;
+        echo "{ \"name\": \"web\" }" > packages/web/package.json;
+    )?;
+
+    // Act
+    assert_cmd::Command::cargo_bin("cog")?
+        .arg("init")
+        .arg("--interactive")
+        .write_stdin("y\nn\n\npre-push\n")
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Detected a npm workspaces"));
+
+    let settings: Settings = toml::from_str(&fs::read_to_string(CONFIG_PATH)?)?;
+    assert_that!(settings.packages.get("web").map(|package| &package.path))
+        .is_equal_to(Some(&PathBuf::from("packages/web")));
+    assert_that!(settings.changelog.remote).is_none();
+    assert_that!(settings.changelog.template).is_none();
+    assert_that!(Path::new(".git/hooks/pre-push")).exists();
+    Ok(())
+}
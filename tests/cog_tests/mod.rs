@@ -2,5 +2,6 @@ mod bump;
 mod changelog;
 mod check;
 mod commit;
+mod config;
 mod init;
 mod verify;
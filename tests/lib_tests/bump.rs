@@ -1,9 +1,10 @@
 use anyhow::Result;
-use cmd_lib::run_cmd;
-use cocogitto::settings::Settings;
-use cocogitto::{conventional::version::IncrementCommand, CocoGitto};
+use cmd_lib::{run_cmd, run_fun};
+use cocogitto::settings::{HookConfig, Settings};
+use cocogitto::{conventional::version::IncrementCommand, CocoGitto, CogError};
 use sealed_test::prelude::*;
 use speculoos::prelude::*;
+use std::path::Path;
 
 use crate::helpers::*;
 
@@ -16,7 +17,7 @@ fn bump_ok() -> Result<()> {
     git_tag("1.0.0")?;
     git_commit("feat: add another feature commit")?;
 
-    let mut cocogitto = CocoGitto::get()?;
+    let cocogitto = CocoGitto::get()?;
 
     // Act
     let result = cocogitto.create_version(IncrementCommand::Auto, None, None, false);
@@ -36,7 +37,7 @@ fn monorepo_bump_ok() -> Result<()> {
 
     init_monorepo(&mut settings)?;
 
-    let mut cocogitto = CocoGitto::get()?;
+    let cocogitto = CocoGitto::get()?;
 
     // Act
     let result = cocogitto.create_monorepo_version(IncrementCommand::Auto, None, None, false);
@@ -60,7 +61,7 @@ fn monorepo_bump_manual_ok() -> Result<()> {
         git tag "one-0.1.0";
     )?;
 
-    let mut cocogitto = CocoGitto::get()?;
+    let cocogitto = CocoGitto::get()?;
 
     // Act
     let result = cocogitto.create_monorepo_version(IncrementCommand::Major, None, None, false);
@@ -81,7 +82,7 @@ fn monorepo_with_tag_prefix_bump_ok() -> Result<()> {
 
     init_monorepo(&mut settings)?;
 
-    let mut cocogitto = CocoGitto::get()?;
+    let cocogitto = CocoGitto::get()?;
 
     // Act
     let result = cocogitto.create_monorepo_version(IncrementCommand::Auto, None, None, false);
@@ -102,7 +103,7 @@ fn package_bump_ok() -> Result<()> {
 
     init_monorepo(&mut settings)?;
     let package = settings.packages.get("one").unwrap();
-    let mut cocogitto = CocoGitto::get()?;
+    let cocogitto = CocoGitto::get()?;
 
     // Act
     let result = cocogitto.create_package_version(
@@ -127,7 +128,7 @@ fn should_fallback_to_0_0_0_when_there_is_no_tag() -> Result<()> {
     git_commit("chore: first commit")?;
     git_commit("feat: add a feature commit")?;
 
-    let mut cocogitto = CocoGitto::get()?;
+    let cocogitto = CocoGitto::get()?;
 
     // Act
     let result = cocogitto.create_version(IncrementCommand::Auto, None, None, false);
@@ -149,7 +150,7 @@ fn should_fallback_to_0_0_0_when_there_is_no_tag() -> Result<()> {
 //     git_tag("toto")?;
 //     git_commit("feat: add another feature commit")?;
 //
-//     let mut cocogitto = CocoGitto::get()?;
+//     let cocogitto = CocoGitto::get()?;
 //
 //     // Act
 //     let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false);
@@ -180,7 +181,7 @@ fn bump_with_whitelisted_branch_ok() -> Result<()> {
     git_commit("chore: first commit")?;
     git_commit("feat: add a feature commit")?;
 
-    let mut cocogitto = CocoGitto::get()?;
+    let cocogitto = CocoGitto::get()?;
 
     // Act
     let result = cocogitto.create_version(IncrementCommand::Auto, None, None, false);
@@ -205,7 +206,7 @@ fn bump_with_whitelisted_branch_fails() -> Result<()> {
     git_commit("chore: first commit")?;
     git_commit("feat: add a feature commit")?;
 
-    let mut cocogitto = CocoGitto::get()?;
+    let cocogitto = CocoGitto::get()?;
 
     // Act
     let result = cocogitto.create_version(IncrementCommand::Auto, None, None, false);
@@ -234,7 +235,7 @@ fn bump_with_whitelisted_branch_pattern_ok() -> Result<()> {
 
     run_cmd!(git checkout -b release/1.0.0;)?;
 
-    let mut cocogitto = CocoGitto::get()?;
+    let cocogitto = CocoGitto::get()?;
 
     // Act
     let result = cocogitto.create_version(IncrementCommand::Auto, None, None, false);
@@ -259,7 +260,7 @@ fn bump_with_whitelisted_branch_pattern_err() -> Result<()> {
     git_commit("chore: first commit")?;
     git_commit("feat: add a feature commit")?;
 
-    let mut cocogitto = CocoGitto::get()?;
+    let cocogitto = CocoGitto::get()?;
 
     // Act
     let result = cocogitto.create_version(IncrementCommand::Auto, None, None, false);
@@ -269,3 +270,149 @@ fn bump_with_whitelisted_branch_pattern_err() -> Result<()> {
 
     Ok(())
 }
+
+#[sealed_test]
+fn plan_bump_does_not_modify_repository() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: first commit")?;
+    git_commit("feat: add a feature commit")?;
+    git_tag("1.0.0")?;
+    git_commit("fix: fix a bug")?;
+
+    let cocogitto = CocoGitto::open(".")?;
+
+    // Act
+    let plan = cocogitto.plan_bump(IncrementCommand::Auto, None)?;
+
+    // Assert
+    assert_that!(plan.current.map(|tag| tag.to_string())).is_equal_to(Some("1.0.0".to_string()));
+    assert_that!(plan.next.to_string()).is_equal_to("1.0.1".to_string());
+    assert_that!(plan.changelog).contains("fix a bug");
+    assert_latest_tag("1.0.0")?;
+    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
+    Ok(())
+}
+
+#[sealed_test]
+fn apply_bump_ok() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: first commit")?;
+    git_commit("feat: add a feature commit")?;
+
+    let cocogitto = CocoGitto::open(".")?;
+    let plan = cocogitto.plan_bump(IncrementCommand::Auto, None)?;
+
+    // Act
+    let bump = cocogitto.apply_bump(&plan, None)?;
+
+    // Assert
+    assert_latest_tag("0.1.0")?;
+    let head = run_fun!(git rev-parse HEAD)?;
+    assert_that!(bump.commit.to_string()).is_equal_to(head);
+    assert_that!(bump.changelog).contains("add a feature commit");
+    Ok(())
+}
+
+#[sealed_test]
+fn apply_bump_returns_hook_failure() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: first commit")?;
+    git_commit("feat: add a feature commit")?;
+
+    let settings = Settings {
+        pre_bump_hooks: vec![HookConfig::Command("exit 1".to_string())],
+        ..Default::default()
+    };
+
+    let cocogitto = CocoGitto::with_settings(".", settings)?;
+    let plan = cocogitto.plan_bump(IncrementCommand::Auto, None)?;
+
+    // Act
+    let result = cocogitto.apply_bump(&plan, None);
+
+    // Assert
+    assert!(matches!(result, Err(CogError::HookFailed { .. })));
+    assert_tag_does_not_exist("0.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn apply_bump_returns_pre_changelog_hook_failure() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: first commit")?;
+    git_commit("feat: add a feature commit")?;
+
+    let settings = Settings {
+        pre_changelog_hooks: vec![HookConfig::Command("exit 1".to_string())],
+        ..Default::default()
+    };
+
+    let cocogitto = CocoGitto::with_settings(".", settings)?;
+    let plan = cocogitto.plan_bump(IncrementCommand::Auto, None)?;
+
+    // Act
+    let result = cocogitto.apply_bump(&plan, None);
+
+    // Assert
+    assert!(matches!(result, Err(CogError::HookFailed { .. })));
+    assert_tag_does_not_exist("0.1.0")?;
+    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
+    Ok(())
+}
+
+#[sealed_test]
+fn apply_bump_fails_on_dirty_working_tree() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: first commit")?;
+    git_commit("feat: add a feature commit")?;
+
+    let cocogitto = CocoGitto::open(".")?;
+    let plan = cocogitto.plan_bump(IncrementCommand::Auto, None)?;
+    run_cmd!(echo changes > file)?;
+
+    // Act
+    let result = cocogitto.apply_bump(&plan, None);
+
+    // Assert
+    assert!(matches!(result, Err(CogError::DirtyWorkingTree(_))));
+    Ok(())
+}
+
+#[sealed_test]
+fn apply_bump_in_repository_subdirectory() -> Result<()> {
+    // Arrange
+    run_cmd!(
+        git init -q -b master repository;
+        git -C repository config --local user.name Tom;
+        git -C repository config --local user.email toml.bombadil@themail.org;
+        git -C repository commit --allow-empty -q -m "chore: first commit";
+        git -C repository commit --allow-empty -q -m "feat: add a feature commit";
+    )?;
+
+    let settings = Settings {
+        pre_bump_hooks: vec![HookConfig::Command("touch hook_ran".to_string())],
+        ..Default::default()
+    };
+
+    let cocogitto = CocoGitto::with_settings("repository", settings)?;
+    let plan = cocogitto.plan_bump(IncrementCommand::Auto, None)?;
+
+    // Act
+    cocogitto.apply_bump(&plan, None)?;
+
+    // Assert
+    assert_that!(Path::new("repository/CHANGELOG.md")).exists();
+    assert_that!(Path::new("repository/hook_ran")).exists();
+    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
+    assert_that!(Path::new("hook_ran")).does_not_exist();
+    let changed = run_fun!(git -C repository show --name-only --format= HEAD)?;
+    assert_that!(changed).contains("CHANGELOG.md");
+    let tags = run_fun!(git -C repository tag)?;
+    assert_that!(tags).is_equal_to("0.1.0".to_string());
+    Ok(())
+}
@@ -2,9 +2,13 @@ use crate::helpers::*;
 
 use anyhow::Result;
 use cmd_lib::run_cmd;
-use cocogitto::CocoGitto;
+use cocogitto::conventional::changelog::error::ChangelogError;
+use cocogitto::git::revspec::RevspecPattern;
+use cocogitto::settings::Settings;
+use cocogitto::{CocoGitto, CogError};
 use sealed_test::prelude::*;
 use speculoos::prelude::*;
+use std::error::Error;
 
 #[sealed_test]
 fn open_repo_ok() -> Result<()> {
@@ -33,6 +37,45 @@ fn open_repo_err() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+fn open_repo_err_has_source() -> Result<()> {
+    // Act
+    let err = CocoGitto::open("not_a_repository").err();
+
+    // Assert
+    let source = err
+        .as_ref()
+        .and_then(|err| err.source())
+        .map(ToString::to_string);
+    assert_that!(source)
+        .is_some()
+        .contains("failed to open repository");
+    Ok(())
+}
+
+#[sealed_test(env = [
+    ("XDG_CONFIG_HOME", "user_config"),
+    ("COG_NO_USER_CONFIG", ""),
+    ("COG_TAG_PREFIX", "env-"),
+])]
+fn open_reads_repository_config_only() -> Result<()> {
+    // Arrange
+    git_init()?;
+    std::fs::create_dir_all("user_config/cog")?;
+    std::fs::write("user_config/cog/cog.toml", "ignore_merge_commits = true\n")?;
+    std::fs::write("cog.toml", "from_latest_tag = true\n")?;
+
+    // Act
+    let cocogitto = CocoGitto::open(".")?;
+
+    // Assert
+    let settings = cocogitto.settings();
+    assert_that!(settings.from_latest_tag).is_true();
+    assert_that!(settings.ignore_merge_commits).is_false();
+    assert_that!(settings.tag_prefix).is_none();
+    Ok(())
+}
+
 #[sealed_test]
 fn check_commit_history_ok() -> Result<()> {
     // Arrange
@@ -42,7 +85,7 @@ fn check_commit_history_ok() -> Result<()> {
     let cocogitto = CocoGitto::get()?;
 
     // Act
-    let check = cocogitto.check(false, false);
+    let check = cocogitto.check(false, false, None);
 
     // Assert
     assert_that!(check).is_ok();
@@ -58,7 +101,7 @@ fn check_commit_history_err_with_merge_commit() -> Result<()> {
     let cocogitto = CocoGitto::get()?;
 
     // Act
-    let check = cocogitto.check(false, false);
+    let check = cocogitto.check(false, false, None);
 
     // Assert
     assert_that!(check).is_err();
@@ -81,7 +124,7 @@ fn check_commit_history_ok_with_merge_commit_ignored() -> Result<()> {
     let cocogitto = CocoGitto::get()?;
 
     // Act
-    let check = cocogitto.check(false, true);
+    let check = cocogitto.check(false, true, None);
 
     // Assert
     assert_that!(check).is_ok();
@@ -98,7 +141,7 @@ fn check_commit_history_err() -> Result<()> {
     let cocogitto = CocoGitto::get()?;
 
     // Act
-    let check = cocogitto.check(false, false);
+    let check = cocogitto.check(false, false, None);
 
     // Assert
     assert_that!(check).is_err();
@@ -117,7 +160,7 @@ fn check_commit_ok_from_latest_tag() -> Result<()> {
     let cocogitto = CocoGitto::get()?;
 
     // Act
-    let check = cocogitto.check(true, false);
+    let check = cocogitto.check(true, false, None);
 
     // Assert
     assert_that!(check).is_ok();
@@ -135,13 +178,51 @@ fn check_commit_err_from_latest_tag() -> Result<()> {
     let cocogitto = CocoGitto::get()?;
 
     // Act
-    let check = cocogitto.check(true, false);
+    let check = cocogitto.check(true, false, None);
 
     // Assert
     assert_that!(check).is_err();
     Ok(())
 }
 
+#[sealed_test]
+fn check_commit_range_ok() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("this one should not be picked")?;
+    let from = git_commit("feat: first commit")?;
+    git_commit("feat: another commit")?;
+    let cocogitto = CocoGitto::get()?;
+
+    // Act
+    let check = cocogitto.check(
+        false,
+        false,
+        Some(RevspecPattern::from((from.as_str(), "HEAD"))),
+    );
+
+    // Assert
+    assert_that!(check).is_ok();
+    Ok(())
+}
+
+#[sealed_test]
+fn check_commit_range_with_unknown_revision_is_err() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("feat: first commit")?;
+    let cocogitto = CocoGitto::get()?;
+
+    // Act
+    let check = cocogitto.check(false, false, Some(RevspecPattern::from("unknown..HEAD")));
+
+    // Assert
+    assert_that!(check)
+        .is_err()
+        .matches(|err| err.to_string().contains("revision `unknown` not found"));
+    Ok(())
+}
+
 #[sealed_test]
 fn long_commit_summary_does_not_panic() -> Result<()> {
     git_init()?;
@@ -152,8 +233,116 @@ fn long_commit_summary_does_not_panic() -> Result<()> {
     git_add("Hello", "file")?;
     cocogitto.conventional_commit("feat", None, message, None, None, false, false)?;
 
-    let check = cocogitto.check(false, false);
+    let check = cocogitto.check(false, false, None);
 
     assert_that!(check.is_ok());
     Ok(())
 }
+
+#[sealed_test]
+fn check_repositories_with_their_own_settings() -> Result<()> {
+    // Arrange
+    run_cmd!(
+        git init -q one;
+        git -C one commit --allow-empty -q -m "wip: a custom commit type";
+        git init -q two;
+        git -C two commit --allow-empty -q -m "wip: a custom commit type";
+    )?;
+
+    let settings: Settings = toml::from_str("[commit_types]\nwip = { changelog_title = \"WIP\" }")?;
+    let one = CocoGitto::with_settings("one", settings)?;
+    let two = CocoGitto::with_settings("two", Settings::default())?;
+
+    // Act
+    let check_one = one.check(false, false, None);
+    let check_two = two.check(false, false, None);
+
+    // Assert
+    assert_that!(check_one).is_ok();
+    assert_that!(check_two).is_err();
+    Ok(())
+}
+
+#[sealed_test]
+fn repositories_infer_their_own_origin_remote() -> Result<()> {
+    // Arrange
+    run_cmd!(
+        git init -q one;
+        git -C one remote add origin "https://github.com/cocogitto/one.git";
+        git init -q two;
+        git -C two remote add origin "https://gitlab.com/cogit/two.git";
+    )?;
+
+    // Act
+    let one = CocoGitto::with_settings("one", Settings::default())?;
+    let two = CocoGitto::with_settings("two", Settings::default())?;
+
+    // Assert
+    assert_that!(one.settings().changelog.remote).is_equal_to(Some("github.com".to_string()));
+    assert_that!(one.settings().changelog.repository).is_equal_to(Some("one".to_string()));
+    assert_that!(two.settings().changelog.remote).is_equal_to(Some("gitlab.com".to_string()));
+    assert_that!(two.settings().changelog.owner).is_equal_to(Some("cogit".to_string()));
+    Ok(())
+}
+
+#[sealed_test]
+fn check_commits_reports_non_compliant_commits() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("feat: a valid commit")?;
+    git_commit("this is not a conventional commit")?;
+
+    let cocogitto = CocoGitto::open(".")?;
+
+    // Act
+    let outcome = cocogitto.check_commits(None, false)?;
+
+    // Assert
+    assert_that!(outcome.checked).is_equal_to(2);
+    assert_that!(outcome.errors).has_length(1);
+    assert_that!(outcome.is_compliant()).is_false();
+    Ok(())
+}
+
+#[sealed_test]
+fn changelog_renders_changes_since_latest_tag() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("feat: a feature")?;
+    git_tag("1.0.0")?;
+    git_commit("fix: a bug fix")?;
+
+    let cocogitto = CocoGitto::open(".")?;
+
+    // Act
+    let changelog = cocogitto.changelog(RevspecPattern::default())?;
+
+    // Assert
+    assert_that!(changelog).contains("a bug fix");
+    assert_that!(changelog).does_not_contain("a feature");
+    Ok(())
+}
+
+#[sealed_test]
+fn rewrite_changelog_version_without_release_section_is_err() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("feat: first feature")?;
+    git_tag("1.0.0")?;
+    std::fs::write(
+        "CHANGELOG.md",
+        "# Changelog\n- - -\n## 0.1.0 - 2020-01-01\n- - -\n",
+    )?;
+    let cocogitto = CocoGitto::get()?;
+
+    // Act
+    let result = cocogitto.rewrite_changelog_version("1.0.0");
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(CogError::Changelog(ChangelogError::ReleaseNotFound { ref version, .. })) if version == "1.0.0"
+    ));
+    Ok(())
+}